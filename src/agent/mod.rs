@@ -39,14 +39,32 @@ pub fn build_sensei(
         .preamble(&preamble)
         .tool(GetPlayerPosition::new(rcon.clone()))
         .tool(GetPlayerInventory::new(rcon.clone()))
+        .tool(FindItem::new(rcon.clone()))
         .tool(GetProductionStats::new(rcon.clone()))
+        .tool(GetProductionOverview::new(rcon.clone()))
+        .tool(GetFluidProduction::new(rcon.clone()))
+        .tool(GetFluidSystem::new(rcon.clone()))
         .tool(GetPowerStats::new(rcon.clone()))
+        .tool(GetPollution::new(rcon.clone()))
+        .tool(GetDefense::new(rcon.clone()))
+        .tool(GetAlerts::new(rcon.clone()))
         .tool(GetResearchStatus::new(rcon.clone()))
+        .tool(GetTechnology::new(rcon.clone()))
+        .tool(GetLabs::new(rcon.clone()))
         .tool(GetNearbyEntities::new(rcon.clone()))
         .tool(GetNearbyResources::new(rcon.clone()))
+        .tool(GetBelts::new(rcon.clone()))
         .tool(GetAssemblers::new(rcon.clone()))
         .tool(GetFurnaces::new(rcon.clone()))
+        .tool(GetInserters::new(rcon.clone()))
+        .tool(GetMiningDrills::new(rcon.clone()))
+        .tool(GetTrains::new(rcon.clone()))
+        .tool(GetTrainStops::new(rcon.clone()))
+        .tool(GetLogisticNetworks::new(rcon.clone()))
+        .tool(GetConstructionBacklog::new(rcon.clone()))
         .tool(GetRecipe::new(rcon.clone()))
+        .tool(SolveOilBalance::new(rcon.clone()))
+        .tool(ListSurfaces::new(rcon.clone()))
         .default_max_turns(10)
         .build()
 }
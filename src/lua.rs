@@ -9,9 +9,92 @@
 /// 3. Build plain Lua tables (no userdata) for JSON serialization
 /// 4. Use DOT syntax for Factorio 2.x API
 /// 5. Cap entity results to avoid huge responses
+/// 6. Resolve the target surface with [`select_surface`] instead of reading `p.surface`
 const PLAYER_CHECK: &str =
     "local p = game.connected_players[1] if not p then return {error=\"no_player\"} end";
 
+/// Lua snippet building `status_names`, a reverse lookup of `defines.entity_status`
+/// so `status_names[e.status]` yields e.g. `"working"` or `"no_ingredients"`.
+const STATUS_NAMES: &str = "local status_names = {} \
+     for k, v in pairs(defines.entity_status) do status_names[v] = k end";
+
+/// Lua snippet defining `contents(inv)`: the inventory's `{name, count}` list, or
+/// `nil` when the inventory is missing or empty.
+const CONTENTS_FN: &str = "local function contents(inv) \
+       if not inv then return nil end \
+       local out = {} \
+       for _, it in ipairs(inv.get_contents()) do out[#out+1] = {name=it.name, count=it.count} end \
+       if #out == 0 then return nil end \
+       return out \
+     end";
+
+/// Lua snippet defining `fuel_seconds(e)`: how long a burner entity can keep running
+/// at full power on its burning fuel plus fuel inventory, or `nil` if it has no burner.
+const FUEL_SECONDS_FN: &str = "local function fuel_seconds(e) \
+       local burner = e.burner \
+       if not burner then return nil end \
+       local energy = burner.remaining_burning_fuel \
+       local inv = burner.inventory \
+       if inv then \
+         for _, it in ipairs(inv.get_contents()) do \
+           energy = energy + it.count * prototypes.item[it.name].fuel_value \
+         end \
+       end \
+       local usage = e.prototype.get_max_energy_usage(e.quality) \
+       if not usage or usage <= 0 then return nil end \
+       return energy / (usage * 60) \
+     end";
+
+/// Lua snippet defining `compass(dx, dy)`: the 8-way compass direction of an offset
+/// in map coordinates (y grows southward), e.g. `"north"` or `"southwest"`.
+const COMPASS_FN: &str = "local function compass(dx, dy) \
+       local dirs = {\"north\", \"northeast\", \"east\", \"southeast\", \"south\", \"southwest\", \"west\", \"northwest\"} \
+       local deg = math.deg(math.atan2(dx, -dy)) % 360 \
+       return dirs[math.floor((deg + 22.5) / 45) % 8 + 1] \
+     end";
+
+/// Lua snippet defining the player alert helpers. Must follow `PLAYER_CHECK`.
+///
+/// `each_alert_list(filter, fn)` calls `fn(surface_index, alert_type, list)` for
+/// every list `p.get_alerts(filter)` returns; `alert_info(a)` describes one alert
+/// with the affected entity (or alert icon) name, position, age, and custom text.
+const ALERTS_FN: &str = "local function each_alert_list(filter, fn) \
+       for surface_index, by_type in pairs(p.get_alerts(filter)) do \
+         for kind, list in pairs(by_type) do fn(surface_index, kind, list) end \
+       end \
+     end \
+     local function alert_info(a) \
+       return { \
+         name=(a.target and a.target.valid and a.target.name) \
+           or (a.prototype and a.prototype.name) \
+           or (a.icon and a.icon.name) or nil, \
+         x=a.position and a.position.x or nil, \
+         y=a.position and a.position.y or nil, \
+         seconds_ago=(game.tick - a.tick) / 60, \
+         message=type(a.message) == \"string\" and a.message or nil \
+       } \
+     end";
+
+/// Lua snippet binding `s` (the target surface) and `origin` (the search center).
+///
+/// Without a surface name this is the player's surface and position. With one,
+/// the surface is looked up by name and `origin` falls back to the map origin
+/// when the player is standing on a different surface. Must follow `PLAYER_CHECK`.
+fn select_surface(surface: Option<&str>) -> String {
+    surface.map_or_else(
+        || "local s = p.surface local origin = p.position".to_string(),
+        |name| {
+            let safe_name = sanitize_lua_string(name);
+            format!(
+                "local s = game.get_surface(\"{safe_name}\") \
+                 if not s then return {{error=\"unknown surface: {safe_name}\"}} end \
+                 local origin = p.position \
+                 if s.index ~= p.surface.index then origin = {{x=0, y=0}} end"
+            )
+        },
+    )
+}
+
 pub fn player_position() -> String {
     format!(
         "(function() {PLAYER_CHECK} \
@@ -48,43 +131,150 @@ pub fn player_inventory() -> String {
     )
 }
 
-pub fn production_stats(item: &str) -> String {
+pub fn production_stats(
+    item: &str,
+    precision: &str,
+    window_minutes: f64,
+    surface: Option<&str>,
+) -> String {
     // Sanitize item name to prevent Lua injection
     let safe_item = sanitize_lua_string(item);
+    let surface = select_surface(surface);
     format!(
-        "(function() {PLAYER_CHECK} \
-         local stats = p.force.get_item_production_statistics(\"nauvis\") \
+        "(function() {PLAYER_CHECK} {surface} \
+         local stats = p.force.get_item_production_statistics(s) \
          local produced = stats.get_input_count(\"{safe_item}\") \
          local consumed = stats.get_output_count(\"{safe_item}\") \
-         return {{item=\"{safe_item}\", produced=produced, consumed=consumed}} \
+         local prec = defines.flow_precision_index.{precision} \
+         local win_in = stats.get_flow_count{{name=\"{safe_item}\", category=\"input\", precision_index=prec, count=true}} \
+         local win_out = stats.get_flow_count{{name=\"{safe_item}\", category=\"output\", precision_index=prec, count=true}} \
+         return {{item=\"{safe_item}\", produced=produced, consumed=consumed, \
+           produced_per_min=win_in/{window_minutes}, consumed_per_min=win_out/{window_minutes}}} \
          end)()"
     )
 }
 
-pub fn power_stats() -> String {
+pub fn production_overview(precision: &str, window_minutes: f64, surface: Option<&str>) -> String {
+    flow_overview(
+        "get_item_production_statistics",
+        precision,
+        window_minutes,
+        true,
+        None,
+        surface,
+    )
+}
+
+/// Fluid flow rows for every fluid; `fluid` additionally gets its all-time totals.
+pub fn fluid_production_overview(
+    precision: &str,
+    window_minutes: f64,
+    fluid: Option<&str>,
+    surface: Option<&str>,
+) -> String {
+    flow_overview(
+        "get_fluid_production_statistics",
+        precision,
+        window_minutes,
+        false,
+        fluid,
+        surface,
+    )
+}
+
+/// Per-name window rates for every entry of a force flow statistics object.
+/// With `active_only`, names without any flow inside the window are skipped.
+/// Only the `totals_for` row carries all-time `produced`/`consumed` counts.
+fn flow_overview(
+    stats_getter: &str,
+    precision: &str,
+    window_minutes: f64,
+    active_only: bool,
+    totals_for: Option<&str>,
+    surface: Option<&str>,
+) -> String {
+    let surface = select_surface(surface);
+    let totals_for = totals_for.map_or_else(
+        || "nil".to_string(),
+        |name| format!("\"{}\"", sanitize_lua_string(name)),
+    );
     format!(
-        "(function() {PLAYER_CHECK} \
-         local poles = p.surface.find_entities_filtered{{type=\"electric-pole\", limit=1}} \
-         if #poles == 0 then \
-           return {{production_watts=0, consumption_watts=0, satisfaction=1.0}} \
+        "(function() {PLAYER_CHECK} {surface} \
+         local stats = p.force.{stats_getter}(s) \
+         local prec = defines.flow_precision_index.{precision} \
+         local totals_for = {totals_for} \
+         local names = {{}} \
+         for name, _ in pairs(stats.input_counts) do names[name] = true end \
+         for name, _ in pairs(stats.output_counts) do names[name] = true end \
+         local items = {{}} \
+         for name, _ in pairs(names) do \
+           local win_in = stats.get_flow_count{{name=name, category=\"input\", precision_index=prec, count=true}} \
+           local win_out = stats.get_flow_count{{name=name, category=\"output\", precision_index=prec, count=true}} \
+           if not {active_only} or win_in > 0 or win_out > 0 then \
+             local row = {{name=name, produced_per_min=win_in/{window_minutes}, consumed_per_min=win_out/{window_minutes}}} \
+             if name == totals_for then \
+               row.produced = stats.get_input_count(name) \
+               row.consumed = stats.get_output_count(name) \
+             end \
+             items[#items+1] = row \
+           end \
          end \
-         local stats = poles[1].electric_network_statistics \
-         if not stats then \
-           return {{production_watts=0, consumption_watts=0, satisfaction=1.0}} \
+         return {{items=#items > 0 and items or nil}} \
+         end)()"
+    )
+}
+
+pub fn power_stats(surface: Option<&str>) -> String {
+    let surface = select_surface(surface);
+    format!(
+        "(function() {PLAYER_CHECK} {surface} \
+         local poles = s.find_entities_filtered{{type=\"electric-pole\", force=p.force}} \
+         local nets = {{}} \
+         local order = {{}} \
+         for _, pole in ipairs(poles) do \
+           local id = pole.electric_network_id \
+           if id then \
+             local n = nets[id] \
+             if not n then \
+               n = {{id=id, poles=0, stats=pole.electric_network_statistics, acc_count=0, acc_energy=0, acc_capacity=0}} \
+               nets[id] = n \
+               order[#order+1] = n \
+             end \
+             n.poles = n.poles + 1 \
+           end \
+         end \
+         for _, a in ipairs(s.find_entities_filtered{{type=\"accumulator\", force=p.force}}) do \
+           local n = a.electric_network_id and nets[a.electric_network_id] \
+           if n then \
+             n.acc_count = n.acc_count + 1 \
+             n.acc_energy = n.acc_energy + a.energy \
+             n.acc_capacity = n.acc_capacity + a.electric_buffer_size \
+           end \
          end \
          local prec = defines.flow_precision_index.five_seconds \
-         local prod = 0 \
-         for name, _ in pairs(stats.output_counts) do \
-           prod = prod + stats.get_flow_count{{name=name, category=\"output\", precision_index=prec}} \
-         end \
-         local cons = 0 \
-         for name, _ in pairs(stats.input_counts) do \
-           cons = cons + stats.get_flow_count{{name=name, category=\"input\", precision_index=prec}} \
-         end \
-         local satisfaction = 1.0 \
-         if cons > 0 then satisfaction = math.min(1.0, prod / cons) end \
-         return {{production_watts=prod*60, consumption_watts=cons*60, satisfaction=satisfaction, \
-           note=\"satisfaction 1.0 means all demand is met (good). Below 1.0 means brownouts. production_watts is max available capacity, consumption_watts is current demand.\"}} \
+         local result = {{}} \
+         for _, n in ipairs(order) do \
+           local stats = n.stats \
+           local prod = 0 \
+           local generators = {{}} \
+           for name, _ in pairs(stats.output_counts) do \
+             local flow = stats.get_flow_count{{name=name, category=\"output\", precision_index=prec}} \
+             if flow > 0 then generators[#generators+1] = {{name=name, watts=flow*60}} end \
+             prod = prod + flow \
+           end \
+           local cons = 0 \
+           for name, _ in pairs(stats.input_counts) do \
+             cons = cons + stats.get_flow_count{{name=name, category=\"input\", precision_index=prec}} \
+           end \
+           local satisfaction = 1.0 \
+           if cons > 0 then satisfaction = math.min(1.0, prod / cons) end \
+           local charge = nil \
+           if n.acc_capacity > 0 then charge = n.acc_energy / n.acc_capacity end \
+           result[#result+1] = {{id=n.id, poles=n.poles, production_watts=prod*60, consumption_watts=cons*60, \
+             satisfaction=satisfaction, generators=#generators > 0 and generators or nil, accumulators=n.acc_count, \
+             accumulator_charge=charge}} \
+         end \
+         return {{networks=#result > 0 and result or nil}} \
          end)()"
     )
 }
@@ -131,111 +321,1277 @@ pub fn recipe(name: &str) -> String {
     )
 }
 
-pub fn nearby_entities(radius: f64) -> String {
+/// Technology details plus the inputs for a research queue ETA.
+///
+/// With a name, `technology` describes that tech; without one it describes the
+/// current research (and is absent when idle). `queue` lists every queued tech
+/// with its unit cost, and `labs`/`lab_speed` cover the force's labs on all
+/// surfaces. `packs` reports the queue's science packs: stock sitting in labs and
+/// force-wide production/consumption per minute over the last 10 minutes.
+pub fn technology(name: Option<&str>) -> String {
+    let lookup = name.map_or_else(
+        || "local t = force.current_research".to_string(),
+        |name| {
+            let safe_name = sanitize_lua_string(name);
+            format!(
+                "local t = force.technologies[\"{safe_name}\"] \
+                 if not t then return {{error=\"technology_not_found\"}} end"
+            )
+        },
+    );
     format!(
         "(function() {PLAYER_CHECK} \
-         local ents = p.surface.find_entities_filtered{{position=p.position, radius={radius}}} \
+         local force = p.force \
+         {lookup} \
+         local function ingredients(tech) \
+           local out = {{}} \
+           for _, ing in ipairs(tech.research_unit_ingredients) do \
+             out[#out+1] = {{name=ing.name, amount=ing.amount}} \
+           end \
+           return out \
+         end \
          local result = {{}} \
-         local count = 0 \
-         for _, e in ipairs(ents) do \
-           if count >= 50 then break end \
-           if e.type ~= \"resource\" and e.type ~= \"tree\" and e.type ~= \"simple-entity\" then \
-             result[#result+1] = {{name=e.name, type=e.type, x=e.position.x, y=e.position.y}} \
-             count = count + 1 \
+         if t then \
+           local prereqs = {{}} \
+           for pname, pt in pairs(t.prerequisites) do \
+             prereqs[#prereqs+1] = {{name=pname, researched=pt.researched}} \
            end \
+           local unlocks = {{}} \
+           for _, eff in ipairs(t.prototype.effects or {{}}) do \
+             if eff.type == \"unlock-recipe\" then unlocks[#unlocks+1] = eff.recipe end \
+           end \
+           local trigger = t.prototype.research_trigger \
+           result.technology = {{ \
+             name=t.name, \
+             level=t.level, \
+             researched=t.researched, \
+             prerequisites=#prereqs > 0 and prereqs or nil, \
+             unit_count=t.research_unit_count, \
+             unit_time=t.research_unit_energy / 60, \
+             ingredients=#t.research_unit_ingredients > 0 and ingredients(t) or nil, \
+             unlocks_recipes=#unlocks > 0 and unlocks or nil, \
+             trigger=trigger and trigger.type or nil \
+           }} \
          end \
-         return {{entities=result}} \
+         local queue = {{}} \
+         local pack_names = {{}} \
+         for i, tech in ipairs(force.research_queue or {{}}) do \
+           local progress = force.get_saved_technology_progress(tech) or 0 \
+           if i == 1 and force.current_research and force.current_research.name == tech.name then \
+             progress = force.research_progress \
+           end \
+           local ings = ingredients(tech) \
+           for _, ing in ipairs(ings) do pack_names[ing.name] = true end \
+           queue[#queue+1] = {{ \
+             name=tech.name, \
+             progress=progress, \
+             unit_count=tech.research_unit_count, \
+             unit_time=tech.research_unit_energy / 60, \
+             ingredients=#ings > 0 and ings or nil \
+           }} \
+         end \
+         result.queue = #queue > 0 and queue or nil \
+         local labs, lab_speed, stock = 0, 0, {{}} \
+         for _, surf in pairs(game.surfaces) do \
+           for _, e in ipairs(surf.find_entities_filtered{{type=\"lab\", force=force}}) do \
+             labs = labs + 1 \
+             local fx = e.effects or {{}} \
+             lab_speed = lab_speed + e.prototype.get_researching_speed(e.quality) \
+               * (1 + force.laboratory_speed_modifier + (fx.speed or 0)) \
+             local inv = e.get_inventory(defines.inventory.lab_input) \
+             if inv then \
+               for _, it in ipairs(inv.get_contents()) do \
+                 stock[it.name] = (stock[it.name] or 0) + it.count \
+               end \
+             end \
+           end \
+         end \
+         result.labs = labs \
+         result.lab_speed = lab_speed \
+         local packs = {{}} \
+         for pack in pairs(pack_names) do \
+           local produced, consumed = 0, 0 \
+           for _, surf in pairs(game.surfaces) do \
+             local stats = force.get_item_production_statistics(surf) \
+             produced = produced + stats.get_flow_count{{name=pack, category=\"input\", precision_index=defines.flow_precision_index.ten_minutes, count=true}} \
+             consumed = consumed + stats.get_flow_count{{name=pack, category=\"output\", precision_index=defines.flow_precision_index.ten_minutes, count=true}} \
+           end \
+           packs[#packs+1] = {{name=pack, stock=stock[pack] or 0, produced_per_min=produced / 10, consumed_per_min=consumed / 10}} \
+         end \
+         result.packs = #packs > 0 and packs or nil \
+         return result \
          end)()"
     )
 }
 
-pub fn nearby_resources(radius: f64) -> String {
+/// Which entities [`nearby_entities`] and [`entity_census`] select.
+///
+/// Without names or types, resources, trees, and simple entities (rocks) are
+/// excluded so player-built structures are not drowned out.
+pub struct EntityFilter<'a> {
+    /// Entity prototype names; empty matches any.
+    pub names: &'a [String],
+    /// Entity types; empty matches any.
+    pub types: &'a [String],
+    /// Bounding box `(left, top, right, bottom)`. Takes precedence over `radius`.
+    pub area: Option<(f64, f64, f64, f64)>,
+    /// Radius around `origin`; `None` with no area searches the whole surface.
+    pub radius: Option<f64>,
+}
+
+impl EntityFilter<'_> {
+    /// Lua statement binding `filter` for `find_entities_filtered`/`count_entities_filtered`.
+    fn to_lua(&self) -> String {
+        let mut fields = Vec::new();
+        if let Some((left, top, right, bottom)) = self.area {
+            fields.push(format!("area={{{{{left}, {top}}}, {{{right}, {bottom}}}}}"));
+        } else if let Some(radius) = self.radius {
+            fields.push(format!("position=origin, radius={radius}"));
+        }
+        if !self.names.is_empty() {
+            fields.push(format!("name={}", lua_string_list(self.names)));
+        }
+        if !self.types.is_empty() {
+            fields.push(format!("type={}", lua_string_list(self.types)));
+        }
+        if self.names.is_empty() && self.types.is_empty() {
+            fields
+                .push("type={\"resource\", \"tree\", \"simple-entity\"}, invert=true".to_string());
+        }
+        format!("local filter = {{{}}}", fields.join(", "))
+    }
+}
+
+/// Entities matching `filter`, one page of `limit` starting after `cursor`.
+///
+/// Matches are ordered by position (top to bottom, then left to right) so every
+/// page slices the same order. `total` counts every match; `next_cursor` is set
+/// while more pages remain.
+pub fn nearby_entities(
+    filter: &EntityFilter,
+    cursor: u32,
+    limit: u32,
+    surface: Option<&str>,
+) -> String {
+    let surface = select_surface(surface);
+    let filter = filter.to_lua();
     format!(
-        "(function() {PLAYER_CHECK} \
-         local ents = p.surface.find_entities_filtered{{type=\"resource\", position=p.position, radius={radius}}} \
-         local grouped = {{}} \
+        "(function() {PLAYER_CHECK} {surface} {filter} \
+         local ents = s.find_entities_filtered(filter) \
+         table.sort(ents, function(a, b) \
+           local pa, pb = a.position, b.position \
+           if pa.y ~= pb.y then return pa.y < pb.y end \
+           if pa.x ~= pb.x then return pa.x < pb.x end \
+           return a.name < b.name \
+         end) \
+         local result = {{}} \
+         local last = math.min(#ents, {cursor} + {limit}) \
+         for i = {cursor} + 1, last do \
+           local e = ents[i] \
+           result[#result+1] = {{name=e.name, type=e.type, x=e.position.x, y=e.position.y}} \
+         end \
+         return {{ \
+           entities=#result > 0 and result or nil, \
+           total=#ents, \
+           next_cursor=last < #ents and last or nil \
+         }} \
+         end)()"
+    )
+}
+
+/// Entity counts by prototype name for everything matching `filter`.
+///
+/// Tallies one `find_entities_filtered` pass instead of a count per prototype, so
+/// the cost follows the entities actually present. Never truncated.
+pub fn entity_census(filter: &EntityFilter, surface: Option<&str>) -> String {
+    let surface = select_surface(surface);
+    let filter = filter.to_lua();
+    format!(
+        "(function() {PLAYER_CHECK} {surface} {filter} \
+         local ents = s.find_entities_filtered(filter) \
+         local counts = {{}} \
+         for _, e in ipairs(ents) do counts[e.name] = (counts[e.name] or 0) + 1 end \
+         return {{total=#ents, counts=next(counts) and counts or nil}} \
+         end)()"
+    )
+}
+
+/// Resource tiles around `origin`, clustered per resource into connected patches.
+///
+/// Ore tiles join a patch when within 2 tiles of each other (bridging the sparse
+/// edges of real patches); infinite resources such as crude oil use a 12-tile reach
+/// because wells are scattered. Infinite patches report `yield_percent` instead of
+/// `total_amount`. Patches are sorted nearest first.
+pub fn nearby_resources(radius: f64, surface: Option<&str>) -> String {
+    let surface = select_surface(surface);
+    format!(
+        "(function() {PLAYER_CHECK} {surface} {COMPASS_FN} \
+         local ents = s.find_entities_filtered{{type=\"resource\", position=origin, radius={radius}}} \
+         local function key(x, y) return x * 4194304 + y end \
+         local by_name = {{}} \
          for _, e in ipairs(ents) do \
-           local key = e.name \
-           if not grouped[key] then \
-             grouped[key] = {{name=key, total_amount=0, sum_x=0, sum_y=0, count=0}} \
+           local b = by_name[e.name] \
+           if not b then \
+             b = {{cells={{}}, list={{}}, infinite=e.prototype.infinite_resource, normal=e.prototype.normal_resource_amount}} \
+             by_name[e.name] = b \
            end \
-           local g = grouped[key] \
-           g.total_amount = g.total_amount + e.amount \
-           g.sum_x = g.sum_x + e.position.x \
-           g.sum_y = g.sum_y + e.position.y \
-           g.count = g.count + 1 \
+           local cx, cy = math.floor(e.position.x), math.floor(e.position.y) \
+           b.list[#b.list+1] = {{x=cx, y=cy, amount=e.amount}} \
+           b.cells[key(cx, cy)] = #b.list \
          end \
          local result = {{}} \
-         for _, g in pairs(grouped) do \
-           result[#result+1] = {{ \
-             name=g.name, \
-             total_amount=g.total_amount, \
-             center_x=g.sum_x/g.count, \
-             center_y=g.sum_y/g.count \
-           }} \
+         for name, b in pairs(by_name) do \
+           local reach = b.infinite and 12 or 2 \
+           local visited = {{}} \
+           for i, t in ipairs(b.list) do \
+             if not visited[i] then \
+               visited[i] = true \
+               local stack = {{i}} \
+               local g = {{tiles=0, amount=0, sum_x=0, sum_y=0, left=t.x, top=t.y, right=t.x, bottom=t.y}} \
+               while #stack > 0 do \
+                 local c = b.list[table.remove(stack)] \
+                 g.tiles = g.tiles + 1 \
+                 g.amount = g.amount + c.amount \
+                 g.sum_x = g.sum_x + c.x + 0.5 \
+                 g.sum_y = g.sum_y + c.y + 0.5 \
+                 g.left = math.min(g.left, c.x) \
+                 g.top = math.min(g.top, c.y) \
+                 g.right = math.max(g.right, c.x) \
+                 g.bottom = math.max(g.bottom, c.y) \
+                 for dx = -reach, reach do \
+                   for dy = -reach, reach do \
+                     local k = b.cells[key(c.x + dx, c.y + dy)] \
+                     if k and not visited[k] then \
+                       visited[k] = true \
+                       stack[#stack+1] = k \
+                     end \
+                   end \
+                 end \
+               end \
+               local center_x, center_y = g.sum_x / g.tiles, g.sum_y / g.tiles \
+               local dx, dy = center_x - origin.x, center_y - origin.y \
+               local patch = {{ \
+                 name=name, \
+                 tile_count=g.tiles, \
+                 left=g.left, top=g.top, right=g.right + 1, bottom=g.bottom + 1, \
+                 center_x=center_x, \
+                 center_y=center_y, \
+                 distance=math.sqrt(dx * dx + dy * dy), \
+                 direction=compass(dx, dy) \
+               }} \
+               if b.infinite then \
+                 patch.yield_percent = g.amount / b.normal * 100 \
+               else \
+                 patch.total_amount = g.amount \
+               end \
+               result[#result+1] = patch \
+             end \
+           end \
          end \
-         return {{resources=result}} \
+         table.sort(result, function(a, b) return a.distance < b.distance end) \
+         return {{resources=#result > 0 and result or nil}} \
          end)()"
     )
 }
 
-pub fn assemblers(limit: u32) -> String {
+pub fn assemblers(limit: u32, surface: Option<&str>) -> String {
+    let surface = select_surface(surface);
     format!(
-        "(function() {PLAYER_CHECK} \
-         local ents = p.surface.find_entities_filtered{{type=\"assembling-machine\", limit={limit}}} \
+        "(function() {PLAYER_CHECK} {surface} {STATUS_NAMES} {CONTENTS_FN} \
+         local ents = s.find_entities_filtered{{type=\"assembling-machine\", limit={limit}}} \
          local result = {{}} \
          for _, e in ipairs(ents) do \
            local recipe_name = nil \
            local r = e.get_recipe() \
            if r then recipe_name = r.name end \
+           local beacons = e.get_beacons() \
+           local fx = e.effects or {{}} \
            result[#result+1] = {{ \
              name=e.name, \
              x=e.position.x, \
              y=e.position.y, \
              recipe=recipe_name, \
-             crafting_speed=e.crafting_speed \
+             crafting_speed=e.crafting_speed, \
+             status=status_names[e.status], \
+             modules=contents(e.get_module_inventory()), \
+             beacons=beacons and #beacons or 0, \
+             effects={{speed=fx.speed or 0, productivity=fx.productivity or 0, consumption=fx.consumption or 0, \
+               pollution=fx.pollution or 0, quality=fx.quality or 0}}, \
+             productivity_bonus=e.productivity_bonus \
            }} \
          end \
-         return {{assemblers=result}} \
+         return {{assemblers=#result > 0 and result or nil}} \
          end)()"
     )
 }
 
-pub fn furnaces(limit: u32) -> String {
+/// Every assembler on the surface grouped by recipe, with status counts and, for
+/// machines waiting on ingredients, which ingredients are short.
+pub fn assembler_summary(surface: Option<&str>) -> String {
+    let surface = select_surface(surface);
     format!(
-        "(function() {PLAYER_CHECK} \
-         local ents = p.surface.find_entities_filtered{{type=\"furnace\", limit={limit}}} \
+        "(function() {PLAYER_CHECK} {surface} {STATUS_NAMES} \
+         local ents = s.find_entities_filtered{{type=\"assembling-machine\"}} \
+         local groups = {{}} \
+         local order = {{}} \
+         for _, e in ipairs(ents) do \
+           local r = e.get_recipe() \
+           local key = r and r.name or \"\" \
+           local g = groups[key] \
+           if not g then \
+             g = {{recipe=r and r.name or nil, machines=0, statuses={{}}}} \
+             groups[key] = g \
+             order[#order+1] = g \
+           end \
+           g.machines = g.machines + 1 \
+           local st = status_names[e.status] or \"unknown\" \
+           g.statuses[st] = (g.statuses[st] or 0) + 1 \
+           if r and e.status == defines.entity_status.no_ingredients then \
+             local inv = e.get_inventory(defines.inventory.assembling_machine_input) \
+             for _, ing in ipairs(r.ingredients) do \
+               local have = 0 \
+               if ing.type == \"fluid\" then have = e.get_fluid_count(ing.name) \
+               elseif inv then have = inv.get_item_count(ing.name) end \
+               if have < ing.amount then \
+                 g.missing_ingredients = g.missing_ingredients or {{}} \
+                 g.missing_ingredients[ing.name] = (g.missing_ingredients[ing.name] or 0) + 1 \
+               end \
+             end \
+           end \
+         end \
+         return {{groups=#order > 0 and order or nil}} \
+         end)()"
+    )
+}
+
+pub fn furnaces(limit: u32, surface: Option<&str>) -> String {
+    let surface = select_surface(surface);
+    format!(
+        "(function() {PLAYER_CHECK} {surface} {STATUS_NAMES} {CONTENTS_FN} {FUEL_SECONDS_FN} \
+         local ents = s.find_entities_filtered{{type=\"furnace\", limit={limit}}} \
          local result = {{}} \
          for _, e in ipairs(ents) do \
            local recipe_name = nil \
            local r = e.get_recipe() \
            if r then recipe_name = r.name end \
-           local fuel_type = nil \
-           local fuel_inv = e.get_fuel_inventory() \
-           if fuel_inv then \
-             for i = 1, #fuel_inv do \
-               local stack = fuel_inv[i] \
-               if stack.valid_for_read then fuel_type = stack.name break end \
+           result[#result+1] = {{ \
+             name=e.name, \
+             x=e.position.x, \
+             y=e.position.y, \
+             recipe=recipe_name, \
+             status=status_names[e.status], \
+             fuel=contents(e.get_fuel_inventory()), \
+             fuel_seconds=fuel_seconds(e), \
+             input=contents(e.get_inventory(defines.inventory.furnace_source)), \
+             output=contents(e.get_output_inventory()) \
+           }} \
+         end \
+         return {{furnaces=#result > 0 and result or nil}} \
+         end)()"
+    )
+}
+
+/// Every furnace on the surface grouped by furnace type and recipe, with status
+/// counts, total input/output/fuel items, and the lowest remaining burn time.
+pub fn furnace_summary(surface: Option<&str>) -> String {
+    let surface = select_surface(surface);
+    format!(
+        "(function() {PLAYER_CHECK} {surface} {STATUS_NAMES} {FUEL_SECONDS_FN} \
+         local ents = s.find_entities_filtered{{type=\"furnace\"}} \
+         local groups = {{}} \
+         local order = {{}} \
+         for _, e in ipairs(ents) do \
+           local r = e.get_recipe() \
+           local key = e.name .. \"|\" .. (r and r.name or \"\") \
+           local g = groups[key] \
+           if not g then \
+             g = {{name=e.name, recipe=r and r.name or nil, machines=0, statuses={{}}, input_items=0, output_items=0, fuel_items=0}} \
+             groups[key] = g \
+             order[#order+1] = g \
+           end \
+           g.machines = g.machines + 1 \
+           local st = status_names[e.status] or \"unknown\" \
+           g.statuses[st] = (g.statuses[st] or 0) + 1 \
+           local src = e.get_inventory(defines.inventory.furnace_source) \
+           if src then g.input_items = g.input_items + src.get_item_count() end \
+           local out = e.get_output_inventory() \
+           if out then g.output_items = g.output_items + out.get_item_count() end \
+           local fuel = e.get_fuel_inventory() \
+           if fuel then g.fuel_items = g.fuel_items + fuel.get_item_count() end \
+           local secs = fuel_seconds(e) \
+           if secs then g.min_fuel_seconds = math.min(g.min_fuel_seconds or secs, secs) end \
+         end \
+         return {{groups=#order > 0 and order or nil}} \
+         end)()"
+    )
+}
+
+/// Mining drills on the surface (first `limit` listed individually) plus per-resource
+/// groups covering every drill. Group amounts count each resource tile once even
+/// where drill areas overlap; rates use `mining_speed / mining_time`.
+pub fn mining_drills(limit: u32, surface: Option<&str>) -> String {
+    let surface = select_surface(surface);
+    format!(
+        "(function() {PLAYER_CHECK} {surface} {STATUS_NAMES} \
+         local ents = s.find_entities_filtered{{type=\"mining-drill\", force=p.force}} \
+         local drills = {{}} \
+         local groups = {{}} \
+         local order = {{}} \
+         for _, e in ipairs(ents) do \
+           local target = e.mining_target \
+           local res = target and target.name or nil \
+           local key = res or \"\" \
+           local g = groups[key] \
+           if not g then \
+             g = {{resource=res, drills=0, statuses={{}}, remaining_amount=0, infinite=false, \
+               extraction_per_second=0, output_per_second=0, seen={{}}}} \
+             groups[key] = g \
+             order[#order+1] = g \
+           end \
+           g.drills = g.drills + 1 \
+           local st = status_names[e.status] or \"unknown\" \
+           g.statuses[st] = (g.statuses[st] or 0) + 1 \
+           local speed = e.prototype.mining_speed * (1 + ((e.effects or {{}}).speed or 0)) \
+           local amount = nil \
+           if target then \
+             local rp = target.prototype \
+             local mining_time = rp.mineable_properties.mining_time \
+             local drain = (e.prototype.resource_drain_rate_percent or 100) / 100 \
+             g.infinite = rp.infinite_resource \
+             g.extraction_per_second = g.extraction_per_second + speed / mining_time * drain \
+             g.output_per_second = g.output_per_second + speed / mining_time * (1 + e.productivity_bonus) \
+             local r = e.prototype.mining_drill_radius \
+             local area = {{{{e.position.x - r, e.position.y - r}}, {{e.position.x + r, e.position.y + r}}}} \
+             amount = 0 \
+             for _, t in ipairs(s.find_entities_filtered{{type=\"resource\", name=res, area=area}}) do \
+               amount = amount + t.amount \
+               local tk = t.position.x .. \",\" .. t.position.y \
+               if not g.seen[tk] then \
+                 g.seen[tk] = true \
+                 g.remaining_amount = g.remaining_amount + t.amount \
+               end \
+             end \
+           end \
+           if #drills < {limit} then \
+             drills[#drills+1] = {{name=e.name, x=e.position.x, y=e.position.y, resource=res, \
+               amount=amount, status=status_names[e.status], mining_speed=speed}} \
+           end \
+         end \
+         for _, g in ipairs(order) do g.seen = nil end \
+         return {{drills=#drills > 0 and drills or nil, groups=#order > 0 and order or nil}} \
+         end)()"
+    )
+}
+
+/// Labs on the surface checked against the current research's science packs.
+///
+/// `labs` lists up to `limit` labs with their pack inventory, modules, and the
+/// research packs they are `missing` (a lab missing any pack is `starved`).
+/// `summary` covers every lab: status counts and, per research pack, the total
+/// stock and how many labs have none of it.
+pub fn labs(limit: u32, surface: Option<&str>) -> String {
+    let surface = select_surface(surface);
+    format!(
+        "(function() {PLAYER_CHECK} {surface} {STATUS_NAMES} {CONTENTS_FN} \
+         local force = p.force \
+         local research = force.current_research \
+         local packs = {{}} \
+         if research then \
+           for _, ing in ipairs(research.research_unit_ingredients) do \
+             packs[#packs+1] = {{name=ing.name, amount=ing.amount, stock=0, labs_without=0}} \
+           end \
+         end \
+         local ents = s.find_entities_filtered{{type=\"lab\", force=force}} \
+         local list, statuses, starved = {{}}, {{}}, 0 \
+         for _, e in ipairs(ents) do \
+           local status = status_names[e.status] or \"unknown\" \
+           statuses[status] = (statuses[status] or 0) + 1 \
+           local inv = e.get_inventory(defines.inventory.lab_input) \
+           local missing = {{}} \
+           for _, pk in ipairs(packs) do \
+             local c = inv and inv.get_item_count(pk.name) or 0 \
+             pk.stock = pk.stock + c \
+             if c == 0 then \
+               pk.labs_without = pk.labs_without + 1 \
+               missing[#missing+1] = pk.name \
+             end \
+           end \
+           if #missing > 0 then starved = starved + 1 end \
+           if #list < {limit} then \
+             list[#list+1] = {{ \
+               name=e.name, \
+               x=e.position.x, \
+               y=e.position.y, \
+               status=status_names[e.status], \
+               packs=contents(inv), \
+               modules=contents(e.get_module_inventory()), \
+               missing=#missing > 0 and missing or nil, \
+               starved=#missing > 0 \
+             }} \
+           end \
+         end \
+         return {{ \
+           research=research and research.name or nil, \
+           labs=#list > 0 and list or nil, \
+           summary={{ \
+             labs=#ents, \
+             starved=starved, \
+             statuses=next(statuses) and statuses or nil, \
+             packs=#packs > 0 and packs or nil \
+           }} \
+         }} \
+         end)()"
+    )
+}
+
+/// Trains on the surface with state, stations, schedule, cargo, and locomotive fuel.
+///
+/// `states` counts every train by `defines.train_state` name; `trains` lists up
+/// to `limit` of them. `next_station` is the schedule record after the one the
+/// train is waiting at, or the record it is heading to while moving.
+pub fn trains(limit: u32, surface: Option<&str>) -> String {
+    let surface = select_surface(surface);
+    format!(
+        "(function() {PLAYER_CHECK} {surface} {CONTENTS_FN} {FUEL_SECONDS_FN} \
+         local state_names = {{}} \
+         for k, v in pairs(defines.train_state) do state_names[v] = k end \
+         local all = game.train_manager.get_trains{{surface=s, force=p.force}} \
+         local states = {{}} \
+         local result = {{}} \
+         for _, t in ipairs(all) do \
+           local state = state_names[t.state] or \"unknown\" \
+           states[state] = (states[state] or 0) + 1 \
+           if #result < {limit} then \
+             local schedule, next_station = {{}}, nil \
+             local sched = t.get_schedule() \
+             local records = sched.get_records() or {{}} \
+             for _, r in ipairs(records) do \
+               schedule[#schedule+1] = r.station or \"(temporary stop)\" \
+             end \
+             if #records > 0 then \
+               local idx = sched.current \
+               if t.station then idx = idx % #records + 1 end \
+               local r = records[idx] \
+               next_station = r and r.station or nil \
+             end \
+             local cargo = {{}} \
+             for _, it in ipairs(t.get_contents()) do cargo[#cargo+1] = {{name=it.name, count=it.count}} end \
+             local fluids = {{}} \
+             for name, amount in pairs(t.get_fluid_contents()) do fluids[#fluids+1] = {{name=name, amount=amount}} end \
+             local locos = {{}} \
+             for _, group in pairs(t.locomotives) do \
+               for _, l in ipairs(group) do \
+                 locos[#locos+1] = {{name=l.name, fuel=contents(l.get_fuel_inventory()), fuel_seconds=fuel_seconds(l)}} \
+               end \
+             end \
+             local front = t.front_stock \
+             result[#result+1] = {{ \
+               id=t.id, \
+               state=state, \
+               manual_mode=t.manual_mode, \
+               speed=t.speed, \
+               x=front and front.position.x or nil, \
+               y=front and front.position.y or nil, \
+               station=t.station and t.station.backer_name or nil, \
+               next_station=next_station, \
+               schedule=#schedule > 0 and schedule or nil, \
+               cargo=#cargo > 0 and cargo or nil, \
+               fluids=#fluids > 0 and fluids or nil, \
+               locomotives=#locos > 0 and locos or nil \
+             }} \
+           end \
+         end \
+         return {{total=#all, states=next(states) and states or nil, trains=#result > 0 and result or nil}} \
+         end)()"
+    )
+}
+
+/// Train stops on the surface, optionally only those named `name`.
+///
+/// Each stop reports its trains limit (`nil` when unset), trains en route, the
+/// train stopped at it, and its circuit enable/disable condition. `tick` lets
+/// later [`train_stop_occupancy`] samples measure game time.
+pub fn train_stops(name: Option<&str>, surface: Option<&str>) -> String {
+    let surface = select_surface(surface);
+    let filter = name.map_or_else(
+        || "nil".to_string(),
+        |name| format!("\"{}\"", sanitize_lua_string(name)),
+    );
+    format!(
+        "(function() {PLAYER_CHECK} {surface} \
+         local filter = {filter} \
+         local result = {{}} \
+         for _, e in ipairs(s.find_entities_filtered{{type=\"train-stop\", force=p.force}}) do \
+           if not filter or e.backer_name == filter then \
+             local circuit = nil \
+             local cb = e.get_control_behavior() \
+             if cb then \
+               local c = cb.circuit_condition \
+               local condition = nil \
+               if cb.circuit_enable_disable and c and c.first_signal then \
+                 local rhs = c.second_signal and c.second_signal.name or tostring(c.constant or 0) \
+                 condition = c.first_signal.name .. \" \" .. c.comparator .. \" \" .. rhs \
+               end \
+               circuit = {{ \
+                 enable_disable=cb.circuit_enable_disable, \
+                 condition=condition, \
+                 disabled=cb.disabled, \
+                 sets_trains_limit=cb.set_trains_limit \
+               }} \
+             end \
+             local train = e.get_stopped_train() \
+             result[#result+1] = {{ \
+               id=e.unit_number, \
+               name=e.backer_name, \
+               x=e.position.x, \
+               y=e.position.y, \
+               trains_limit=e.trains_limit < 4294967295 and e.trains_limit or nil, \
+               trains_count=e.trains_count, \
+               stopped_train=train and train.id or nil, \
+               circuit=circuit \
+             }} \
+           end \
+         end \
+         return {{tick=game.tick, stops=#result > 0 and result or nil}} \
+         end)()"
+    )
+}
+
+/// Which train (if any) is stopped at each train stop on the surface, keyed by
+/// the stop's `unit_number`. Polled repeatedly to count arrivals.
+pub fn train_stop_occupancy(surface: Option<&str>) -> String {
+    let surface = select_surface(surface);
+    format!(
+        "(function() {PLAYER_CHECK} {surface} \
+         local result = {{}} \
+         for _, e in ipairs(s.find_entities_filtered{{type=\"train-stop\", force=p.force}}) do \
+           local train = e.get_stopped_train() \
+           result[#result+1] = {{id=e.unit_number, train=train and train.id or nil}} \
+         end \
+         return {{tick=game.tick, stops=#result > 0 and result or nil}} \
+         end)()"
+    )
+}
+
+/// The force's logistic networks on the surface: robots, roboports, charging
+/// queue, storage contents, and unfulfilled requests.
+///
+/// A request is unfulfilled when the requester holds less than its filter count
+/// even after counting items robots are already delivering. Storage lists the 30
+/// largest stacks and unfulfilled requests are capped at 30 per network.
+pub fn logistic_networks(surface: Option<&str>) -> String {
+    let surface = select_surface(surface);
+    format!(
+        "(function() {PLAYER_CHECK} {surface} \
+         local result = {{}} \
+         for _, n in ipairs(p.force.logistic_networks[s.name] or {{}}) do \
+           local roboports, charging, waiting, pos = 0, 0, 0, nil \
+           for _, cell in ipairs(n.cells) do \
+             if cell.owner.type == \"roboport\" then \
+               roboports = roboports + 1 \
+               pos = pos or cell.owner.position \
              end \
+             charging = charging + cell.charging_robot_count \
+             waiting = waiting + cell.to_charge_robot_count \
            end \
-           local output_item = nil \
-           local output_inv = e.get_output_inventory() \
-           if output_inv then \
-             for i = 1, #output_inv do \
-               local stack = output_inv[i] \
-               if stack.valid_for_read then output_item = stack.name break end \
+           local storage = {{}} \
+           for _, it in ipairs(n.get_contents()) do storage[#storage+1] = {{name=it.name, count=it.count}} end \
+           table.sort(storage, function(a, b) return a.count > b.count end) \
+           for i = #storage, 31, -1 do storage[i] = nil end \
+           local unfulfilled = {{}} \
+           for _, e in ipairs(n.requesters) do \
+             local pt = e.get_requester_point() \
+             local inv = e.type == \"character\" and e.get_inventory(defines.inventory.character_main) \
+               or e.get_inventory(defines.inventory.chest) \
+             if pt and pt.enabled and inv then \
+               local en_route = pt.targeted_items_deliver or {{}} \
+               for _, f in ipairs(pt.filters or {{}}) do \
+                 if f.name and f.count and f.count > 0 and #unfulfilled < 30 then \
+                   local present = inv.get_item_count(f.name) \
+                   local coming = en_route[f.name] or 0 \
+                   if present + coming < f.count then \
+                     unfulfilled[#unfulfilled+1] = {{ \
+                       requester=e.name, \
+                       x=e.position.x, \
+                       y=e.position.y, \
+                       item=f.name, \
+                       requested=f.count, \
+                       present=present, \
+                       en_route=coming, \
+                       in_network=n.get_item_count(f.name) \
+                     }} \
+                   end \
+                 end \
+               end \
              end \
            end \
            result[#result+1] = {{ \
+             id=n.network_id, \
+             x=pos and pos.x or nil, \
+             y=pos and pos.y or nil, \
+             roboports=roboports, \
+             available_construction_robots=n.available_construction_robots, \
+             total_construction_robots=n.all_construction_robots, \
+             available_logistic_robots=n.available_logistic_robots, \
+             total_logistic_robots=n.all_logistic_robots, \
+             charging_robots=charging, \
+             waiting_to_charge=waiting, \
+             storage=#storage > 0 and storage or nil, \
+             unfulfilled=#unfulfilled > 0 and unfulfilled or nil \
+           }} \
+         end \
+         return {{networks=#result > 0 and result or nil}} \
+         end)()"
+    )
+}
+
+/// Construction backlog: entity/tile ghosts plus entities marked for deconstruction
+/// or upgrade, within `radius` of `origin` or (without a radius) on the whole surface.
+///
+/// `items` lists what the ghosts and upgrades need with the count stored in the
+/// logistic networks covering them. Ghosts outside any construction area are
+/// counted in `out_of_coverage` since robots will never reach them.
+pub fn construction(radius: Option<f64>, surface: Option<&str>) -> String {
+    let surface = select_surface(surface);
+    let area = radius.map_or_else(String::new, |r| format!("position=origin, radius={r}, "));
+    format!(
+        "(function() {PLAYER_CHECK} {surface} \
+         local force = p.force \
+         local needed, order, networks = {{}}, {{}}, {{}} \
+         local out_of_coverage = 0 \
+         local function need(e, proto) \
+           local items = proto and proto.items_to_place_this \
+           local item = items and items[1] \
+           if not item then return end \
+           if not needed[item.name] then \
+             needed[item.name] = 0 \
+             order[#order+1] = item.name \
+           end \
+           needed[item.name] = needed[item.name] + (item.count or 1) \
+           local n = s.find_logistic_networks_by_construction_area(e.position, force)[1] \
+           if n then networks[n.network_id] = n else out_of_coverage = out_of_coverage + 1 end \
+         end \
+         local ghosts = s.find_entities_filtered{{{area}type=\"entity-ghost\", force=force}} \
+         for _, g in ipairs(ghosts) do need(g, g.ghost_prototype) end \
+         local tiles = s.find_entities_filtered{{{area}type=\"tile-ghost\", force=force}} \
+         for _, g in ipairs(tiles) do need(g, g.ghost_prototype) end \
+         local upgrades = s.find_entities_filtered{{{area}to_be_upgraded=true, force=force}} \
+         for _, e in ipairs(upgrades) do need(e, e.get_upgrade_target()) end \
+         local deconstruct = s.find_entities_filtered{{{area}to_be_deconstructed=true}} \
+         local robots = 0 \
+         for _, n in pairs(networks) do robots = robots + n.available_construction_robots end \
+         local items = {{}} \
+         for _, name in ipairs(order) do \
+           local available = 0 \
+           for _, n in pairs(networks) do available = available + n.get_item_count(name) end \
+           items[#items+1] = {{name=name, needed=needed[name], available=available}} \
+         end \
+         return {{ \
+           entity_ghosts=#ghosts, \
+           tile_ghosts=#tiles, \
+           to_upgrade=#upgrades, \
+           to_deconstruct=#deconstruct, \
+           out_of_coverage=out_of_coverage, \
+           available_construction_robots=robots, \
+           items=#items > 0 and items or nil \
+         }} \
+         end)()"
+    )
+}
+
+/// Belts around a point, or the belt path starting at it when `follow` is set.
+///
+/// `center` defaults to `origin`. Area mode takes every belt, underground, and
+/// splitter within `radius`; follow mode walks downstream through
+/// `belt_neighbours` (and underground pairs) until the path ends or loops. Each
+/// belt reports its shape, `belt_speed`, and the item count and contents of every
+/// transport line (2 on belts, 4 on undergrounds, 8 on splitters). At most
+/// `limit` belts are returned.
+pub fn belts(
+    center: Option<(f64, f64)>,
+    radius: f64,
+    follow: bool,
+    limit: u32,
+    surface: Option<&str>,
+) -> String {
+    let surface = select_surface(surface);
+    let center = center.map_or_else(
+        || "origin".to_string(),
+        |(x, y)| format!("{{x={x}, y={y}}}"),
+    );
+    format!(
+        "(function() {PLAYER_CHECK} {surface} \
+         local center = {center} \
+         local types = {{\"transport-belt\", \"underground-belt\", \"splitter\"}} \
+         local function lane(e, i) \
+           local line = e.get_transport_line(i) \
+           local items = {{}} \
+           for _, it in ipairs(line.get_contents()) do items[#items+1] = {{name=it.name, count=it.count}} end \
+           return {{count=line.get_item_count(), items=#items > 0 and items or nil}} \
+         end \
+         local function info(e) \
+           local lanes = {{}} \
+           for i = 1, e.get_max_transport_line_index() do lanes[i] = lane(e, i) end \
+           return {{ \
              name=e.name, \
              x=e.position.x, \
              y=e.position.y, \
-             recipe=recipe_name, \
-             fuel_type=fuel_type, \
-             output_item=output_item \
+             shape=e.type == \"transport-belt\" and e.belt_shape or \"straight\", \
+             speed=e.prototype.belt_speed, \
+             lanes=lanes \
            }} \
          end \
-         return {{furnaces=result}} \
+         local result = {{}} \
+         if {follow} then \
+           local e = s.find_entities_filtered{{position=center, radius=1, type=types, limit=1}}[1] \
+           if not e then return {{error=\"no belt at that position\"}} end \
+           local seen = {{}} \
+           while e and #result < {limit} and not seen[e.unit_number] do \
+             seen[e.unit_number] = true \
+             result[#result+1] = info(e) \
+             if e.type == \"underground-belt\" and e.belt_to_ground_type == \"input\" then \
+               e = e.neighbours \
+             else \
+               e = e.belt_neighbours.outputs[1] \
+             end \
+           end \
+         else \
+           for _, e in ipairs(s.find_entities_filtered{{position=center, radius={radius}, type=types, limit={limit}}}) do \
+             result[#result+1] = info(e) \
+           end \
+         end \
+         return {{belts=#result > 0 and result or nil}} \
+         end)()"
+    )
+}
+
+/// The crafting machine (or lab) at `x, y` and the inserters feeding or unloading it.
+///
+/// Hand size is the force's inserter stack size bonus (bulk inserter capacity
+/// bonus for bulk inserters) plus one, capped by any stack size override. The
+/// machine's recipe comes with its effective crafting speed and productivity so
+/// the required item rates can be computed.
+pub fn inserters_around(x: f64, y: f64, surface: Option<&str>) -> String {
+    let surface = select_surface(surface);
+    format!(
+        "(function() {PLAYER_CHECK} {surface} {STATUS_NAMES} \
+         local force = p.force \
+         local m = s.find_entities_filtered{{position={{x={x}, y={y}}}, radius=1, \
+           type={{\"assembling-machine\", \"furnace\", \"rocket-silo\", \"lab\"}}, limit=1}}[1] \
+         if not m then return {{error=\"no crafting machine or lab at that position\"}} end \
+         local machine = {{name=m.name, x=m.position.x, y=m.position.y}} \
+         if m.type ~= \"lab\" then \
+           machine.crafting_speed = m.crafting_speed \
+           machine.productivity_bonus = m.productivity_bonus \
+           local r = m.get_recipe() \
+           if r then \
+             local ingredients, products = {{}}, {{}} \
+             for _, ing in ipairs(r.ingredients) do \
+               ingredients[#ingredients+1] = {{name=ing.name, type=ing.type, amount=ing.amount}} \
+             end \
+             for _, prod in ipairs(r.products) do \
+               local amount = prod.amount or (prod.amount_min + prod.amount_max) / 2 \
+               products[#products+1] = {{name=prod.name, type=prod.type, amount=amount * (prod.probability or 1)}} \
+             end \
+             machine.recipe = {{name=r.name, energy=r.energy, ingredients=ingredients, products=products}} \
+           end \
+         end \
+         local box = m.bounding_box \
+         local area = {{{{box.left_top.x - 3, box.left_top.y - 3}}, {{box.right_bottom.x + 3, box.right_bottom.y + 3}}}} \
+         local result = {{}} \
+         for _, ins in ipairs(s.find_entities_filtered{{area=area, type=\"inserter\", force=force}}) do \
+           local pickup, drop = ins.pickup_target, ins.drop_target \
+           local role = nil \
+           if drop == m then role = \"input\" elseif pickup == m then role = \"output\" end \
+           if role then \
+             local hand = 1 + (ins.prototype.bulk and force.bulk_inserter_capacity_bonus or force.inserter_stack_size_bonus) \
+             local override = ins.inserter_stack_size_override \
+             if override and override > 0 then hand = math.min(hand, override) end \
+             result[#result+1] = {{ \
+               name=ins.name, \
+               x=ins.position.x, \
+               y=ins.position.y, \
+               role=role, \
+               pickup=pickup and pickup.name or nil, \
+               drop=drop and drop.name or nil, \
+               hand_size=hand, \
+               status=status_names[ins.status] \
+             }} \
+           end \
+         end \
+         return {{machine=machine, inserters=#result > 0 and result or nil}} \
+         end)()"
+    )
+}
+
+/// Enemy evolution on the surface split by cause, pollution sources, and chunk
+/// pollution within `chunk_radius` chunks of `origin`.
+///
+/// `sources` lists each polluting entity name with its type and emission per
+/// minute over the last 10 minutes. `chunks` reports how many scanned chunks hold
+/// pollution, their total, and the 10 worst chunks (center positions).
+pub fn pollution(chunk_radius: u32, surface: Option<&str>) -> String {
+    let surface = select_surface(surface);
+    format!(
+        "(function() {PLAYER_CHECK} {surface} \
+         local enemy = game.forces.enemy \
+         local evo = game.map_settings.enemy_evolution \
+         local stats = game.get_pollution_statistics(s) \
+         local sources = {{}} \
+         for name in pairs(stats.input_counts) do \
+           local per_min = stats.get_flow_count{{name=name, category=\"input\", precision_index=defines.flow_precision_index.ten_minutes, count=true}} / 10 \
+           if per_min > 0 then \
+             local proto = prototypes.entity[name] \
+             sources[#sources+1] = {{name=name, type=proto and proto.type or name, per_minute=per_min}} \
+           end \
+         end \
+         local cx, cy = math.floor(origin.x / 32), math.floor(origin.y / 32) \
+         local scanned, polluted, total = 0, 0, 0 \
+         local hotspots = {{}} \
+         for dx = -{chunk_radius}, {chunk_radius} do \
+           for dy = -{chunk_radius}, {chunk_radius} do \
+             local chunk = {{x=cx + dx, y=cy + dy}} \
+             if s.is_chunk_generated(chunk) then \
+               scanned = scanned + 1 \
+               local pos = {{x=chunk.x * 32 + 16, y=chunk.y * 32 + 16}} \
+               local v = s.get_pollution(pos) \
+               if v > 0 then \
+                 polluted = polluted + 1 \
+                 total = total + v \
+                 hotspots[#hotspots+1] = {{x=pos.x, y=pos.y, pollution=v}} \
+               end \
+             end \
+           end \
+         end \
+         table.sort(hotspots, function(a, b) return a.pollution > b.pollution end) \
+         for i = #hotspots, 11, -1 do hotspots[i] = nil end \
+         return {{ \
+           evolution={{ \
+             factor=enemy.get_evolution_factor(s), \
+             by_time=enemy.get_evolution_factor_by_time(s), \
+             by_pollution=enemy.get_evolution_factor_by_pollution(s), \
+             by_killing_spawners=enemy.get_evolution_factor_by_killing_spawners(s) \
+           }}, \
+           settings={{ \
+             enabled=evo.enabled, \
+             time_factor=evo.time_factor, \
+             pollution_factor=evo.pollution_factor, \
+             destroy_factor=evo.destroy_factor \
+           }}, \
+           sources=#sources > 0 and sources or nil, \
+           chunks={{ \
+             scanned=scanned, \
+             polluted=polluted, \
+             total=total, \
+             hotspots=#hotspots > 0 and hotspots or nil \
+           }} \
+         }} \
+         end)()"
+    )
+}
+
+/// Defense picture within `radius` of `origin`.
+///
+/// `threats` are the nearest 30 enemy spawners and worms that have one of the
+/// force's entities within `distance` tiles. `turrets` lists the nearest 100 of the
+/// force's `turret_count` turrets with ammo, fluid, and electric buffer, health,
+/// and whether walls stand within 6 tiles. Recent `entity_under_attack` and
+/// `entity_destroyed` alerts give the damage history.
+pub fn defense(radius: f64, distance: f64, surface: Option<&str>) -> String {
+    let surface = select_surface(surface);
+    format!(
+        "(function() {PLAYER_CHECK} {surface} {STATUS_NAMES} {CONTENTS_FN} {COMPASS_FN} {ALERTS_FN} \
+         local force = p.force \
+         local function place(e) \
+           local dx, dy = e.position.x - origin.x, e.position.y - origin.y \
+           return math.sqrt(dx * dx + dy * dy), compass(dx, dy) \
+         end \
+         local function nearest(ents, n) \
+           local dist = {{}} \
+           for _, e in ipairs(ents) do dist[e] = place(e) end \
+           table.sort(ents, function(a, b) return dist[a] < dist[b] end) \
+           for i = #ents, n + 1, -1 do ents[i] = nil end \
+           return ents \
+         end \
+         local near, spawners, worms = {{}}, 0, 0 \
+         local enemies = s.find_entities_filtered{{position=origin, radius={radius}, force=\"enemy\", type={{\"unit-spawner\", \"turret\"}}}} \
+         for _, e in ipairs(enemies) do \
+           if s.count_entities_filtered{{position=e.position, radius={distance}, force=force, limit=1}} > 0 then \
+             if e.type == \"unit-spawner\" then spawners = spawners + 1 else worms = worms + 1 end \
+             near[#near+1] = e \
+           end \
+         end \
+         local threats = {{}} \
+         for _, e in ipairs(nearest(near, 30)) do \
+           local d, dir = place(e) \
+           threats[#threats+1] = {{name=e.name, x=e.position.x, y=e.position.y, distance=d, direction=dir}} \
+         end \
+         local turrets = {{}} \
+         local found = s.find_entities_filtered{{position=origin, radius={radius}, force=force, \
+           type={{\"ammo-turret\", \"fluid-turret\", \"electric-turret\", \"artillery-turret\"}}}} \
+         local turret_count = #found \
+         for _, e in ipairs(nearest(found, 100)) do \
+           local d, dir = place(e) \
+           local t = {{ \
+             name=e.name, type=e.type, x=e.position.x, y=e.position.y, distance=d, direction=dir, \
+             status=status_names[e.status], \
+             health_ratio=e.get_health_ratio(), \
+             walled=s.count_entities_filtered{{position=e.position, radius=6, type={{\"wall\", \"gate\"}}, force=force, limit=1}} > 0 \
+           }} \
+           if e.type == \"ammo-turret\" then \
+             t.ammo = contents(e.get_inventory(defines.inventory.turret_ammo)) \
+           elseif e.type == \"artillery-turret\" then \
+             t.ammo = contents(e.get_inventory(defines.inventory.artillery_turret_ammo)) \
+           elseif e.type == \"fluid-turret\" then \
+             local fb = e.fluidbox[1] \
+             t.fluid = fb and {{name=fb.name, amount=fb.amount}} or nil \
+           elseif e.electric_buffer_size and e.electric_buffer_size > 0 then \
+             t.energy_ratio = e.energy / e.electric_buffer_size \
+           end \
+           turrets[#turrets+1] = t \
+         end \
+         local walls = s.count_entities_filtered{{position=origin, radius={radius}, type={{\"wall\", \"gate\"}}, force=force}} \
+         local function alerts(kind) \
+           local out = {{}} \
+           each_alert_list({{surface=s, type=kind}}, function(_, _, list) \
+             for _, a in ipairs(list) do \
+               if #out >= 20 then break end \
+               local info = alert_info(a) \
+               info.name = info.name or \"unknown\" \
+               out[#out+1] = info \
+             end \
+           end) \
+           return #out > 0 and out or nil \
+         end \
+         return {{ \
+           evolution=game.forces.enemy.get_evolution_factor(s), \
+           spawners=spawners, \
+           worms=worms, \
+           threats=#threats > 0 and threats or nil, \
+           turrets=#turrets > 0 and turrets or nil, \
+           turret_count=turret_count, \
+           walls=walls, \
+           under_attack=alerts(defines.alert_type.entity_under_attack), \
+           destroyed=alerts(defines.alert_type.entity_destroyed) \
+         }} \
+         end)()"
+    )
+}
+
+/// The fluid system connected to the entity at (`x`, `y`).
+///
+/// Walks fluidbox connections breadth-first from the first entity with a fluidbox
+/// at the position, visiting up to `limit` entities. Reports fluid segment
+/// contents, storage tanks, pumpjacks with their resource amount and bonuses,
+/// and crafting machines (refineries, chemical plants) with status and every
+/// fluidbox's fluid, amount, capacity, and input/output role.
+pub fn fluid_system(x: f64, y: f64, limit: u32, surface: Option<&str>) -> String {
+    let surface = select_surface(surface);
+    format!(
+        "(function() {PLAYER_CHECK} {surface} {STATUS_NAMES} \
+         local start = nil \
+         for _, e in ipairs(s.find_entities_filtered{{position={{x={x}, y={y}}}, radius=1, force=p.force}}) do \
+           if e.fluidbox and #e.fluidbox > 0 then start = e break end \
+         end \
+         if not start then return {{error=\"no entity with a fluidbox at that position\"}} end \
+         local function boxes(e) \
+           local fb, out = e.fluidbox, {{}} \
+           for i = 1, #fb do \
+             local f, filter, proto = fb[i], fb.get_filter(i), fb.get_prototype(i) \
+             if proto and proto.object_name ~= \"LuaFluidBoxPrototype\" then proto = proto[1] end \
+             out[#out+1] = {{ \
+               fluid=(f and f.name) or (filter and filter.name) or nil, \
+               amount=f and f.amount or 0, \
+               capacity=fb.get_capacity(i), \
+               temperature=f and f.temperature or nil, \
+               production_type=proto and proto.production_type or nil \
+             }} \
+           end \
+           return out \
+         end \
+         local queue, seen, head = {{start}}, {{[start.unit_number]=true}}, 1 \
+         while head <= #queue and #queue < {limit} do \
+           local fb = queue[head].fluidbox \
+           head = head + 1 \
+           for i = 1, #fb do \
+             for _, other in ipairs(fb.get_connections(i)) do \
+               local o = other.owner \
+               if o and o.valid and o.unit_number and not seen[o.unit_number] and #queue < {limit} then \
+                 seen[o.unit_number] = true \
+                 queue[#queue+1] = o \
+               end \
+             end \
+           end \
+         end \
+         local segments, segment_seen, tanks, pumpjacks, machines, other = {{}}, {{}}, {{}}, {{}}, {{}}, {{}} \
+         local pipes = 0 \
+         for _, e in ipairs(queue) do \
+           local fb = e.fluidbox \
+           for i = 1, #fb do \
+             local id = fb.get_fluid_segment_id(i) \
+             if id and not segment_seen[id] then \
+               segment_seen[id] = true \
+               local contents = fb.get_fluid_segment_contents(i) \
+               segments[#segments+1] = {{id=id, fluids=next(contents) and contents or nil}} \
+             end \
+           end \
+           if e.type == \"pipe\" or e.type == \"pipe-to-ground\" then \
+             pipes = pipes + 1 \
+           elseif e.type == \"storage-tank\" then \
+             local f = fb[1] \
+             tanks[#tanks+1] = {{ \
+               name=e.name, x=e.position.x, y=e.position.y, \
+               fluid=f and f.name or nil, amount=f and f.amount or 0, capacity=fb.get_capacity(1) \
+             }} \
+           elseif e.type == \"mining-drill\" then \
+             local t = e.mining_target \
+             pumpjacks[#pumpjacks+1] = {{ \
+               name=e.name, x=e.position.x, y=e.position.y, \
+               status=status_names[e.status], \
+               resource=t and t.name or nil, \
+               amount=t and t.amount or 0, \
+               normal_amount=t and t.prototype.normal_resource_amount or nil, \
+               mining_speed=e.prototype.mining_speed, \
+               speed_bonus=e.speed_bonus, \
+               productivity_bonus=e.productivity_bonus \
+             }} \
+           elseif e.type == \"assembling-machine\" or e.type == \"furnace\" then \
+             local r = e.get_recipe() \
+             machines[#machines+1] = {{ \
+               name=e.name, x=e.position.x, y=e.position.y, \
+               recipe=r and r.name or nil, \
+               status=status_names[e.status], \
+               fluidboxes=boxes(e) \
+             }} \
+           else \
+             other[e.name] = (other[e.name] or 0) + 1 \
+           end \
+         end \
+         return {{ \
+           start={{name=start.name, type=start.type, x=start.position.x, y=start.position.y, fluidboxes=boxes(start)}}, \
+           entities=#queue, \
+           truncated=#queue >= {limit}, \
+           pipes=pipes, \
+           segments=#segments > 0 and segments or nil, \
+           tanks=#tanks > 0 and tanks or nil, \
+           pumpjacks=#pumpjacks > 0 and pumpjacks or nil, \
+           machines=#machines > 0 and machines or nil, \
+           other=next(other) and other or nil \
+         }} \
+         end)()"
+    )
+}
+
+/// Every container holding `item` on the surface, nearest to `origin` first.
+///
+/// Searches chests (including logistic and linked), cargo wagons, and the output
+/// inventories of assemblers and furnaces of the player's force — the whole
+/// surface, not a radius. `by_container` totals the count per container prototype
+/// across every match; `locations` keeps the nearest `limit`.
+pub fn find_item(item: &str, limit: u32, surface: Option<&str>) -> String {
+    let surface = select_surface(surface);
+    let safe_item = sanitize_lua_string(item);
+    format!(
+        "(function() {PLAYER_CHECK} {surface} \
+         local item = \"{safe_item}\" \
+         if not prototypes.item[item] then return {{error=\"unknown item: \" .. item}} end \
+         local inventories = {{ \
+           [\"container\"]=defines.inventory.chest, \
+           [\"logistic-container\"]=defines.inventory.chest, \
+           [\"linked-container\"]=defines.inventory.chest, \
+           [\"cargo-wagon\"]=defines.inventory.cargo_wagon, \
+           [\"assembling-machine\"]=defines.inventory.assembling_machine_output, \
+           [\"furnace\"]=defines.inventory.furnace_result \
+         }} \
+         local types = {{}} \
+         for t in pairs(inventories) do types[#types+1] = t end \
+         local found = s.find_entities_filtered{{force=p.force, type=types}} \
+         local matches, by_container, total = {{}}, {{}}, 0 \
+         for _, e in ipairs(found) do \
+           local inv = e.get_inventory(inventories[e.type]) \
+           local count = inv and inv.get_item_count(item) or 0 \
+           if count > 0 then \
+             total = total + count \
+             by_container[e.name] = (by_container[e.name] or 0) + count \
+             local dx, dy = e.position.x - origin.x, e.position.y - origin.y \
+             matches[#matches+1] = {{ \
+               name=e.name, type=e.type, x=e.position.x, y=e.position.y, \
+               count=count, distance=math.sqrt(dx * dx + dy * dy) \
+             }} \
+           end \
+         end \
+         table.sort(matches, function(a, b) return a.distance < b.distance end) \
+         local locations = {{}} \
+         for i = 1, math.min({limit}, #matches) do locations[i] = matches[i] end \
+         return {{ \
+           item=item, \
+           total=total, \
+           containers=#matches, \
+           locations=#locations > 0 and locations or nil, \
+           by_container=next(by_container) and by_container or nil \
+         }} \
+         end)()"
+    )
+}
+
+/// The player's active alerts, grouped by surface and alert type.
+///
+/// Each group carries its full count and up to 10 alerts with the affected entity
+/// (or alert icon) name, position, and age. Without `surface` every surface is read.
+pub fn alerts(surface: Option<&str>) -> String {
+    let filter = surface.map_or_else(
+        || "local filter = {} ".to_string(),
+        |name| {
+            let safe_name = sanitize_lua_string(name);
+            format!(
+                "local s = game.get_surface(\"{safe_name}\") \
+                 if not s then return {{error=\"unknown surface: {safe_name}\"}} end \
+                 local filter = {{surface=s}} "
+            )
+        },
+    );
+    format!(
+        "(function() {PLAYER_CHECK} {ALERTS_FN} {filter}\
+         local type_names = {{}} \
+         for name, id in pairs(defines.alert_type) do type_names[id] = name end \
+         local groups = {{}} \
+         each_alert_list(filter, function(surface_index, kind, list) \
+           if #list == 0 then return end \
+           local surface = game.surfaces[surface_index] \
+           local sample = {{}} \
+           for _, a in ipairs(list) do \
+             if #sample >= 10 then break end \
+             sample[#sample+1] = alert_info(a) \
+           end \
+           groups[#groups+1] = {{ \
+             alert_type=type_names[kind] or tostring(kind), \
+             surface=surface and surface.name or tostring(surface_index), \
+             count=#list, \
+             alerts=sample \
+           }} \
+         end) \
+         return {{groups=#groups > 0 and groups or nil}} \
+         end)()"
+    )
+}
+
+pub fn surfaces() -> String {
+    format!(
+        "(function() {PLAYER_CHECK} \
+         local result = {{}} \
+         for _, s in pairs(game.surfaces) do \
+           result[#result+1] = {{ \
+             name=s.name, \
+             planet=s.planet and s.planet.name or nil, \
+             platform=s.platform and s.platform.name or nil, \
+             player_here=(s.index == p.surface.index), \
+             entities=s.count_entities_filtered{{force=p.force}}, \
+             crafting_machines=s.count_entities_filtered{{force=p.force, type={{\"assembling-machine\", \"furnace\", \"rocket-silo\"}}}}, \
+             mining_drills=s.count_entities_filtered{{force=p.force, type=\"mining-drill\"}}, \
+             enemies=s.count_entities_filtered{{force=\"enemy\"}} \
+           }} \
+         end \
+         return {{surfaces=result}} \
          end)()"
     )
 }
@@ -250,6 +1606,15 @@ fn sanitize_lua_string(input: &str) -> String {
         .replace(']', "\\]")
 }
 
+/// A Lua array literal of sanitized strings, e.g. `{"a", "b"}`.
+fn lua_string_list(items: &[String]) -> String {
+    let quoted: Vec<String> = items
+        .iter()
+        .map(|i| format!("\"{}\"", sanitize_lua_string(i)))
+        .collect();
+    format!("{{{}}}", quoted.join(", "))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -285,7 +1650,7 @@ mod tests {
 
     #[test]
     fn test_production_stats_uses_dot_syntax() {
-        let lua = production_stats("iron-plate");
+        let lua = production_stats("iron-plate", "ten_minutes", 10.0, None);
         assert!(lua.contains("get_item_production_statistics"));
         assert!(lua.contains("iron-plate"));
         // DOT syntax, not colon
@@ -295,7 +1660,7 @@ mod tests {
 
     #[test]
     fn test_production_stats_sanitizes_input() {
-        let lua = production_stats(r#"iron"; os.execute("rm"#);
+        let lua = production_stats(r#"iron"; os.execute("rm"#, "ten_minutes", 10.0, None);
         // Quotes are escaped so Lua can't break out of the string literal
         assert!(lua.contains(r#"iron\"; os.execute(\"rm"#));
         // The unescaped quote pattern should NOT appear
@@ -303,27 +1668,177 @@ mod tests {
     }
 
     #[test]
-    fn test_nearby_entities_caps_at_50() {
-        let lua = nearby_entities(20.0);
-        assert!(lua.contains("count >= 50"));
+    fn test_production_stats_reads_flow_window() {
+        let lua = production_stats("iron-plate", "one_hour", 60.0, None);
+        assert!(lua.contains("defines.flow_precision_index.one_hour"));
+        assert!(lua.contains("stats.get_flow_count"));
+        assert!(lua.contains("count=true"));
+        assert!(lua.contains("win_in/60"));
+    }
+
+    #[test]
+    fn test_production_overview_walks_all_items() {
+        let lua = production_overview("ten_minutes", 10.0, None);
+        assert!(lua.contains("stats.input_counts"));
+        assert!(lua.contains("stats.output_counts"));
+        assert!(lua.contains("defines.flow_precision_index.ten_minutes"));
+        assert!(lua.contains("win_in/10"));
+        assert!(lua.contains("not true or"));
+        assert!(lua.contains("items=#items > 0 and items or nil"));
+        assert!(lua.contains("local totals_for = nil"));
+    }
+
+    #[test]
+    fn test_fluid_production_overview_uses_fluid_stats() {
+        let lua = fluid_production_overview("one_minute", 1.0, Some("steam"), None);
+        assert!(lua.contains("get_fluid_production_statistics"));
+        assert!(!lua.contains("get_item_production_statistics"));
+        assert!(lua.contains("not false or"));
+        assert!(lua.contains(r#"local totals_for = "steam""#));
+        assert!(lua.contains("get_input_count(name)"));
+    }
+
+    #[test]
+    fn test_technology_current_research() {
+        let lua = technology(None);
+        assert!(lua.contains("local t = force.current_research"));
+        assert!(lua.contains("research_unit_count"));
+        assert!(lua.contains("unlock-recipe"));
+    }
+
+    #[test]
+    fn test_technology_by_name() {
+        let lua = technology(Some("oil-processing"));
+        assert!(lua.contains(r#"force.technologies["oil-processing"]"#));
+        assert!(lua.contains("technology_not_found"));
+    }
+
+    #[test]
+    fn test_technology_sanitizes_name() {
+        let lua = technology(Some(r#"x"]game.tick--"#));
+        assert!(lua.contains(r#"x\"\]game.tick--"#));
+    }
+
+    #[test]
+    fn test_technology_collects_eta_inputs() {
+        let lua = technology(None);
+        assert!(lua.contains("force.research_queue"));
+        assert!(lua.contains("force.get_saved_technology_progress(tech) or 0"));
+        assert!(lua.contains("get_researching_speed(e.quality)"));
+        assert!(lua.contains("laboratory_speed_modifier"));
+        assert!(lua.contains("defines.inventory.lab_input"));
+        assert!(lua.contains("result.queue = #queue > 0 and queue or nil"));
+        assert!(lua.contains("result.packs = #packs > 0 and packs or nil"));
+    }
+
+    fn no_filter() -> EntityFilter<'static> {
+        EntityFilter {
+            names: &[],
+            types: &[],
+            area: None,
+            radius: Some(20.0),
+        }
+    }
+
+    #[test]
+    fn test_nearby_entities_paginates() {
+        let lua = nearby_entities(&no_filter(), 50, 50, None);
+        assert!(lua.contains("math.min(#ents, 50 + 50)"));
+        assert!(lua.contains("next_cursor=last < #ents and last or nil"));
+        assert!(lua.contains("total=#ents"));
+    }
+
+    #[test]
+    fn test_nearby_entities_sorts_before_slicing() {
+        let lua = nearby_entities(&no_filter(), 50, 50, None);
+        let sort = lua.find("table.sort(ents").unwrap();
+        assert!(sort < lua.find("local last").unwrap());
+        assert!(lua.contains("if pa.y ~= pb.y then return pa.y < pb.y end"));
     }
 
     #[test]
     fn test_nearby_entities_filters_noise() {
-        let lua = nearby_entities(20.0);
+        let lua = nearby_entities(&no_filter(), 0, 50, None);
         assert!(lua.contains("resource"));
         assert!(lua.contains("tree"));
         assert!(lua.contains("simple-entity"));
+        assert!(lua.contains("invert=true"));
+    }
+
+    #[test]
+    fn test_entity_filter_names_types_and_area() {
+        let names = ["gun-turret".to_string()];
+        let types = ["ammo-turret".to_string(), "wall".to_string()];
+        let filter = EntityFilter {
+            names: &names,
+            types: &types,
+            area: Some((-10.0, -5.0, 10.0, 5.0)),
+            radius: Some(20.0),
+        };
+        let lua = filter.to_lua();
+        assert!(lua.contains("area={{-10, -5}, {10, 5}}"));
+        assert!(!lua.contains("radius="));
+        assert!(lua.contains("name={\"gun-turret\"}"));
+        assert!(lua.contains("type={\"ammo-turret\", \"wall\"}"));
+        assert!(!lua.contains("invert"));
+    }
+
+    #[test]
+    fn test_entity_filter_whole_surface() {
+        let filter = EntityFilter {
+            radius: None,
+            ..no_filter()
+        };
+        let lua = filter.to_lua();
+        assert!(!lua.contains("position=origin"));
+        assert!(!lua.contains("area="));
+    }
+
+    #[test]
+    fn test_entity_filter_sanitizes_names() {
+        let names = ["x\"} game.print(1) --".to_string()];
+        let filter = EntityFilter {
+            names: &names,
+            ..no_filter()
+        };
+        assert!(filter
+            .to_lua()
+            .contains("name={\"x\\\"} game.print(1) --\"}"));
+    }
+
+    #[test]
+    fn test_entity_census_counts_per_prototype() {
+        let lua = entity_census(&no_filter(), None);
+        assert!(lua.contains("s.find_entities_filtered(filter)"));
+        assert!(lua.contains("counts[e.name] = (counts[e.name] or 0) + 1"));
+        assert!(!lua.contains("prototypes.entity"));
     }
 
     #[test]
     fn test_nearby_resources_aggregates() {
-        let lua = nearby_resources(50.0);
+        let lua = nearby_resources(50.0, None);
         assert!(lua.contains("total_amount"));
         assert!(lua.contains("sum_x"));
         assert!(lua.contains("center_x"));
     }
 
+    #[test]
+    fn test_nearby_resources_clusters_patches() {
+        let lua = nearby_resources(50.0, None);
+        assert!(lua.contains("visited[k]"));
+        assert!(lua.contains("local reach = b.infinite and 12 or 2"));
+        assert!(lua.contains("tile_count=g.tiles"));
+        assert!(lua.contains("direction=compass(dx, dy)"));
+        assert!(lua.contains("resources=#result > 0 and result or nil"));
+    }
+
+    #[test]
+    fn test_nearby_resources_reports_oil_yield() {
+        let lua = nearby_resources(50.0, None);
+        assert!(lua.contains("normal_resource_amount"));
+        assert!(lua.contains("patch.yield_percent"));
+    }
+
     #[test]
     fn test_recipe_no_player_check() {
         let lua = recipe("iron-gear-wheel");
@@ -340,17 +1855,71 @@ mod tests {
 
     #[test]
     fn test_assemblers_respects_limit() {
-        let lua = assemblers(15);
+        let lua = assemblers(15, None);
         assert!(lua.contains("limit=15"));
     }
 
+    #[test]
+    fn test_assemblers_report_status() {
+        let lua = assemblers(15, None);
+        assert!(lua.contains("defines.entity_status"));
+        assert!(lua.contains("status=status_names[e.status]"));
+        assert!(lua.contains("assemblers=#result > 0 and result or nil"));
+    }
+
+    #[test]
+    fn test_assemblers_read_modules_and_beacons() {
+        let lua = assemblers(15, None);
+        assert!(lua.contains("contents(e.get_module_inventory())"));
+        assert!(lua.contains("get_contents()"));
+        assert!(lua.contains("get_beacons()"));
+        assert!(lua.contains("e.effects"));
+        assert!(lua.contains("productivity_bonus=e.productivity_bonus"));
+    }
+
+    #[test]
+    fn test_assembler_summary_groups_all_machines() {
+        let lua = assembler_summary(None);
+        assert!(!lua.contains("limit="));
+        assert!(lua.contains("g.statuses[st]"));
+        assert!(lua.contains("defines.entity_status.no_ingredients"));
+        assert!(lua.contains("missing_ingredients"));
+        assert!(lua.contains("groups=#order > 0 and order or nil"));
+    }
+
+    #[test]
+    fn test_furnaces_report_status() {
+        let lua = furnaces(30, None);
+        assert!(lua.contains("status=status_names[e.status]"));
+        assert!(lua.contains("furnaces=#result > 0 and result or nil"));
+    }
+
     #[test]
     fn test_furnaces_checks_fuel_and_output() {
-        let lua = furnaces(30);
+        let lua = furnaces(30, None);
         assert!(lua.contains("get_fuel_inventory()"));
         assert!(lua.contains("get_output_inventory()"));
     }
 
+    #[test]
+    fn test_furnaces_read_counts_and_burn_time() {
+        let lua = furnaces(30, None);
+        assert!(lua.contains("defines.inventory.furnace_source"));
+        assert!(lua.contains("remaining_burning_fuel"));
+        assert!(lua.contains("fuel_value"));
+        assert!(lua.contains("fuel_seconds=fuel_seconds(e)"));
+    }
+
+    #[test]
+    fn test_furnace_summary_groups_by_type_and_recipe() {
+        let lua = furnace_summary(None);
+        assert!(!lua.contains("limit="));
+        assert!(lua.contains(r#"e.name .. "|" .. "#));
+        assert!(lua.contains("min_fuel_seconds"));
+        assert!(lua.contains("output_items"));
+        assert!(lua.contains("groups=#order > 0 and order or nil"));
+    }
+
     #[test]
     fn test_research_status_handles_queue() {
         let lua = research_status();
@@ -361,8 +1930,9 @@ mod tests {
 
     #[test]
     fn test_power_stats_finds_poles() {
-        let lua = power_stats();
+        let lua = power_stats(None);
         assert!(lua.contains("electric-pole"));
+        assert!(!lua.contains("limit=1"));
         assert!(lua.contains("electric_network_statistics"));
         assert!(lua.contains("get_flow_count"));
         assert!(lua.contains("defines.flow_precision_index.five_seconds"));
@@ -371,6 +1941,320 @@ mod tests {
         assert!(lua.contains("input_counts"));
     }
 
+    #[test]
+    fn test_power_stats_groups_by_network() {
+        let lua = power_stats(None);
+        assert!(lua.contains("electric_network_id"));
+        assert!(lua.contains("generators=#generators > 0 and generators or nil"));
+        assert!(lua.contains("accumulator"));
+        assert!(lua.contains("electric_buffer_size"));
+    }
+
+    #[test]
+    fn test_select_surface_defaults_to_player() {
+        let lua = nearby_entities(&no_filter(), 0, 50, None);
+        assert!(lua.contains("local s = p.surface"));
+        assert!(lua.contains("position=origin"));
+        assert!(!lua.contains("game.get_surface"));
+    }
+
+    #[test]
+    fn test_select_surface_by_name() {
+        let lua = production_stats("iron-plate", "ten_minutes", 10.0, Some("vulcanus"));
+        assert!(lua.contains(r#"game.get_surface("vulcanus")"#));
+        assert!(lua.contains("unknown surface: vulcanus"));
+        assert!(lua.contains("get_item_production_statistics(s)"));
+    }
+
+    #[test]
+    fn test_select_surface_sanitizes_name() {
+        let lua = assemblers(10, Some(r#"x") game.tick --"#));
+        assert!(lua.contains(r#"x\") game.tick --"#));
+        assert!(!lua.contains(r#"x") game"#));
+    }
+
+    #[test]
+    fn test_mining_drills_reads_targets_and_rates() {
+        let lua = mining_drills(20, None);
+        assert!(lua.contains("mining-drill"));
+        assert!(lua.contains("e.mining_target"));
+        assert!(lua.contains("mineable_properties.mining_time"));
+        assert!(lua.contains("resource_drain_rate_percent"));
+        assert!(lua.contains("#drills < 20"));
+    }
+
+    #[test]
+    fn test_mining_drills_dedupes_overlapping_tiles() {
+        let lua = mining_drills(20, None);
+        assert!(lua.contains("g.seen[tk]"));
+        assert!(lua.contains("g.seen = nil"));
+    }
+
+    #[test]
+    fn test_mining_drills_omits_empty_lists() {
+        let lua = mining_drills(20, None);
+        assert!(lua.contains("drills=#drills > 0 and drills or nil"));
+        assert!(lua.contains("groups=#order > 0 and order or nil"));
+    }
+
+    #[test]
+    fn test_labs_checks_research_packs() {
+        let lua = labs(20, None);
+        assert!(lua.contains("research.research_unit_ingredients"));
+        assert!(lua.contains("defines.inventory.lab_input"));
+        assert!(lua.contains("inv.get_item_count(pk.name)"));
+        assert!(lua.contains("#list < 20"));
+        assert!(lua.contains("labs=#list > 0 and list or nil"));
+    }
+
+    #[test]
+    fn test_labs_reports_modules_and_status() {
+        let lua = labs(20, None);
+        assert!(lua.contains("e.get_module_inventory()"));
+        assert!(lua.contains("status_names[e.status]"));
+    }
+
+    #[test]
+    fn test_trains_uses_train_manager() {
+        let lua = trains(30, None);
+        assert!(lua.contains("game.train_manager.get_trains{surface=s, force=p.force}"));
+        assert!(lua.contains("defines.train_state"));
+        assert!(lua.contains("#result < 30"));
+        assert!(lua.contains("trains=#result > 0 and result or nil"));
+    }
+
+    #[test]
+    fn test_trains_reads_schedule_cargo_and_fuel() {
+        let lua = trains(30, None);
+        assert!(lua.contains("t.get_schedule()"));
+        assert!(lua.contains("sched.get_records()"));
+        assert!(!lua.contains("t.schedule"));
+        assert!(lua.contains("t.get_contents()"));
+        assert!(lua.contains("t.get_fluid_contents()"));
+        assert!(lua.contains("fuel_seconds(l)"));
+    }
+
+    #[test]
+    fn test_train_stops_reads_limits_and_circuit() {
+        let lua = train_stops(None, None);
+        assert!(lua.contains("local filter = nil"));
+        assert!(lua.contains("e.trains_limit"));
+        assert!(lua.contains("e.trains_count"));
+        assert!(lua.contains("cb.circuit_enable_disable"));
+        assert!(lua.contains("tick=game.tick"));
+        assert!(lua.contains("stops=#result > 0 and result or nil"));
+    }
+
+    #[test]
+    fn test_train_stops_filters_by_name() {
+        let lua = train_stops(Some(r#"Iron "Drop""#), None);
+        assert!(lua.contains(r#"local filter = "Iron \"Drop\"""#));
+    }
+
+    #[test]
+    fn test_train_stop_occupancy() {
+        let lua = train_stop_occupancy(None);
+        assert!(lua.contains("e.get_stopped_train()"));
+        assert!(lua.contains("id=e.unit_number"));
+    }
+
+    #[test]
+    fn test_logistic_networks_reads_robots_and_roboports() {
+        let lua = logistic_networks(None);
+        assert!(lua.contains("p.force.logistic_networks[s.name]"));
+        assert!(lua.contains("n.available_construction_robots"));
+        assert!(lua.contains("cell.to_charge_robot_count"));
+        assert!(lua.contains("networks=#result > 0 and result or nil"));
+    }
+
+    #[test]
+    fn test_logistic_networks_finds_unfulfilled_requests() {
+        let lua = logistic_networks(None);
+        assert!(lua.contains("e.get_requester_point()"));
+        assert!(lua.contains("pt.targeted_items_deliver"));
+        assert!(lua.contains("present + coming < f.count"));
+        assert!(lua.contains("defines.inventory.character_main"));
+    }
+
+    #[test]
+    fn test_construction_whole_surface() {
+        let lua = construction(None, None);
+        assert!(lua.contains(r#"find_entities_filtered{type="entity-ghost", force=force}"#));
+        assert!(lua.contains(r#"find_entities_filtered{type="tile-ghost", force=force}"#));
+        assert!(lua.contains("to_be_deconstructed=true"));
+        assert!(lua.contains("e.get_upgrade_target()"));
+    }
+
+    #[test]
+    fn test_construction_within_radius() {
+        let lua = construction(Some(64.0), None);
+        assert!(lua.contains(r#"{position=origin, radius=64, type="entity-ghost""#));
+    }
+
+    #[test]
+    fn test_construction_checks_network_stock() {
+        let lua = construction(None, None);
+        assert!(lua.contains("items_to_place_this"));
+        assert!(lua.contains("s.find_logistic_networks_by_construction_area(e.position, force)[1]"));
+        assert!(lua.contains("n.get_item_count(name)"));
+    }
+
+    #[test]
+    fn test_belts_area_mode() {
+        let lua = belts(None, 10.0, false, 100, None);
+        assert!(lua.contains("local center = origin"));
+        assert!(lua.contains("if false then"));
+        assert!(lua.contains("radius=10, type=types, limit=100"));
+        assert!(lua.contains("e.get_transport_line(i)"));
+        assert!(lua.contains("for i = 1, e.get_max_transport_line_index() do"));
+        assert!(lua.contains("belts=#result > 0 and result or nil"));
+    }
+
+    #[test]
+    fn test_belts_follow_mode() {
+        let lua = belts(Some((12.5, -3.5)), 10.0, true, 50, None);
+        assert!(lua.contains("local center = {x=12.5, y=-3.5}"));
+        assert!(lua.contains("if true then"));
+        assert!(lua.contains("e.belt_neighbours.outputs[1]"));
+        assert!(lua.contains("e = e.neighbours"));
+    }
+
+    #[test]
+    fn test_inserters_around_finds_machine() {
+        let lua = inserters_around(10.5, -4.5, None);
+        assert!(lua.contains("position={x=10.5, y=-4.5}, radius=1"));
+        assert!(lua.contains("m.crafting_speed"));
+        assert!(lua.contains("m.get_recipe()"));
+        assert!(lua.contains("inserters=#result > 0 and result or nil"));
+    }
+
+    #[test]
+    fn test_inserters_around_classifies_and_sizes_hands() {
+        let lua = inserters_around(0.0, 0.0, None);
+        assert!(lua.contains("if drop == m then role"));
+        assert!(lua.contains("force.bulk_inserter_capacity_bonus"));
+        assert!(lua.contains("force.inserter_stack_size_bonus"));
+        assert!(lua.contains("ins.inserter_stack_size_override"));
+    }
+
+    #[test]
+    fn test_pollution_splits_evolution() {
+        let lua = pollution(8, None);
+        assert!(lua.contains("enemy.get_evolution_factor(s)"));
+        assert!(lua.contains("enemy.get_evolution_factor_by_time(s)"));
+        assert!(lua.contains("enemy.get_evolution_factor_by_pollution(s)"));
+        assert!(lua.contains("enemy.get_evolution_factor_by_killing_spawners(s)"));
+        assert!(lua.contains("game.map_settings.enemy_evolution"));
+    }
+
+    #[test]
+    fn test_pollution_reads_sources_and_chunks() {
+        let lua = pollution(8, None);
+        assert!(lua.contains("game.get_pollution_statistics(s)"));
+        assert!(lua.contains("for dx = -8, 8 do"));
+        assert!(lua.contains("s.get_pollution(pos)"));
+    }
+
+    #[test]
+    fn test_defense_finds_threats_near_buildings() {
+        let lua = defense(500.0, 100.0, None);
+        assert!(lua.contains(r#"radius=500, force="enemy", type={"unit-spawner", "turret"}"#));
+        assert!(lua.contains("radius=100, force=force, limit=1"));
+        assert!(lua.contains("compass(dx, dy)"));
+        assert!(lua.contains("nearest(near, 30)"));
+    }
+
+    #[test]
+    fn test_defense_checks_turret_readiness() {
+        let lua = defense(500.0, 100.0, None);
+        assert!(lua.contains("defines.inventory.turret_ammo"));
+        assert!(lua.contains("e.fluidbox[1]"));
+        assert!(lua.contains("e.energy / e.electric_buffer_size"));
+        assert!(lua.contains(r#"type={"wall", "gate"}"#));
+        assert!(lua.contains("turret_count=turret_count"));
+        let sort = lua.find("table.sort(ents").unwrap();
+        assert!(sort < lua.find("nearest(found, 100)").unwrap());
+    }
+
+    #[test]
+    fn test_defense_reads_damage_alerts() {
+        let lua = defense(500.0, 100.0, None);
+        assert!(lua.contains("defines.alert_type.entity_under_attack"));
+        assert!(lua.contains("defines.alert_type.entity_destroyed"));
+        assert!(lua.contains(ALERTS_FN));
+        assert!(lua.contains("alert_info(a)"));
+    }
+
+    #[test]
+    fn test_fluid_system_walks_connections() {
+        let lua = fluid_system(10.5, -3.5, 500, None);
+        assert!(lua.contains("position={x=10.5, y=-3.5}"));
+        assert!(lua.contains("fb.get_connections(i)"));
+        assert!(lua.contains("#queue < 500"));
+        assert!(lua.contains("truncated=#queue >= 500"));
+    }
+
+    #[test]
+    fn test_fluid_system_reads_segments_tanks_and_machines() {
+        let lua = fluid_system(0.0, 0.0, 500, None);
+        assert!(lua.contains("fb.get_fluid_segment_contents(i)"));
+        assert!(lua.contains("\"storage-tank\""));
+        assert!(lua.contains("normal_resource_amount"));
+        assert!(lua.contains("production_type"));
+        assert!(lua.contains("status_names[e.status]"));
+    }
+
+    #[test]
+    fn test_find_item_searches_whole_surface() {
+        let lua = find_item("copper-cable", 20, None);
+        assert!(lua.contains("local item = \"copper-cable\""));
+        assert!(lua.contains("s.find_entities_filtered{force=p.force, type=types}"));
+        assert!(!lua.contains("radius="));
+        assert!(lua.contains("math.min(20, #matches)"));
+    }
+
+    #[test]
+    fn test_find_item_reads_container_inventories() {
+        let lua = find_item("iron-plate", 20, None);
+        assert!(lua.contains("defines.inventory.chest"));
+        assert!(lua.contains("defines.inventory.cargo_wagon"));
+        assert!(lua.contains("defines.inventory.assembling_machine_output"));
+        assert!(lua.contains("defines.inventory.furnace_result"));
+        assert!(lua.contains("prototypes.item[item]"));
+    }
+
+    #[test]
+    fn test_find_item_sanitizes_name() {
+        let lua = find_item("x\"] game.print(1) --", 20, None);
+        assert!(lua.contains("local item = \"x\\\"\\] game.print(1) --\""));
+    }
+
+    #[test]
+    fn test_alerts_names_alert_types() {
+        let lua = alerts(None);
+        assert!(lua.contains(ALERTS_FN));
+        assert!(lua.contains("each_alert_list(filter, function"));
+        assert!(lua.contains("pairs(defines.alert_type)"));
+        assert!(lua.contains("count=#list"));
+    }
+
+    #[test]
+    fn test_alerts_filters_surface() {
+        let lua = alerts(Some("vulcanus"));
+        assert!(lua.contains("game.get_surface(\"vulcanus\")"));
+        assert!(lua.contains("filter = {surface=s}"));
+        assert!(!alerts(None).contains("get_surface"));
+    }
+
+    #[test]
+    fn test_surfaces_counts_entities() {
+        let lua = surfaces();
+        assert!(lua.contains("game.surfaces"));
+        assert!(lua.contains("count_entities_filtered"));
+        assert!(lua.contains("s.planet"));
+        assert!(lua.contains("s.platform"));
+    }
+
     #[test]
     fn test_sanitize_lua_string() {
         assert_eq!(sanitize_lua_string("iron-plate"), "iron-plate");
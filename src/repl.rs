@@ -16,7 +16,7 @@ const DIM: &str = "\x1b[2m";
 const GRAY: &str = "\x1b[38;5;245m";
 const RESET: &str = "\x1b[0m";
 
-const STATUS_PROMPT: &str = "Give me a quick status overview: check my position, \
+const STATUS_PROMPT: &str = "Give me a quick status overview: check active alerts, my position, \
     power grid, current research, and production of iron-plate and copper-plate.";
 
 // ── Custom prompt ──────────────────────────────────────────────
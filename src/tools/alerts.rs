@@ -0,0 +1,191 @@
+//! Tool for reading the player's active alerts.
+//!
+//! Factorio already tracks what needs attention — destroyed or attacked entities,
+//! turrets out of ammo, missing construction materials, trains out of fuel — in
+//! the alert panel. This returns those alerts grouped by surface and type with a
+//! count and sample positions.
+//!
+//! Groups are ordered on the Rust side so the most urgent alert types come first.
+
+use rig::{completion::ToolDefinition, tool::Tool};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    error::SenseiError,
+    lua,
+    rcon_ext::{execute_lua_json, SharedRcon},
+};
+
+/// `defines.alert_type` names, most urgent first. Unlisted types sort last.
+const ALERT_PRIORITY: &[&str] = &[
+    "entity_destroyed",
+    "entity_under_attack",
+    "turret_out_of_ammo",
+    "turret_fire",
+    "train_out_of_fuel",
+    "train_no_path",
+    "no_material_for_construction",
+    "not_enough_construction_robots",
+    "not_enough_repair_packs",
+    "no_storage",
+    "no_roboport_storage",
+    "no_platform_storage",
+    "platform_tile_building_blocked",
+    "collector_path_blocked",
+    "pipeline_overextended",
+    "unclaimed_cargo",
+    "custom",
+];
+
+fn priority(alert_type: &str) -> usize {
+    ALERT_PRIORITY
+        .iter()
+        .position(|t| *t == alert_type)
+        .unwrap_or(ALERT_PRIORITY.len())
+}
+
+/// Reads the alerts shown in the player's alert panel.
+pub struct GetAlerts {
+    pub(crate) rcon: SharedRcon,
+}
+
+impl GetAlerts {
+    pub const fn new(rcon: SharedRcon) -> Self {
+        Self { rcon }
+    }
+}
+
+/// Arguments for [`GetAlerts`]. All optional.
+#[derive(Debug, Deserialize)]
+pub struct GetAlertsArgs {
+    /// Only alerts on this surface (planet or space platform). Defaults to every surface.
+    pub surface: Option<String>,
+}
+
+/// A single alert.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct AlertInfo {
+    /// Affected entity or alert icon name (e.g. "gun-turret", "locomotive").
+    pub name: Option<String>,
+    pub x: Option<f64>,
+    pub y: Option<f64>,
+    /// Seconds since the alert was raised.
+    pub seconds_ago: f64,
+    /// Text of custom alerts.
+    pub message: Option<String>,
+}
+
+/// Alerts of one type on one surface.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct AlertGroup {
+    /// `defines.alert_type` name (e.g. "turret_out_of_ammo").
+    pub alert_type: String,
+    pub surface: String,
+    /// Total alerts of this type on the surface.
+    pub count: u32,
+    /// Up to 10 of them.
+    #[serde(default)]
+    pub alerts: Vec<AlertInfo>,
+}
+
+/// Top-level response: alert groups, most urgent first.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct Alerts {
+    #[serde(default)]
+    pub groups: Vec<AlertGroup>,
+    /// Alerts across all groups.
+    #[serde(default)]
+    pub total: u32,
+}
+
+impl Alerts {
+    fn sort(&mut self) {
+        self.groups.sort_by(|a, b| {
+            priority(&a.alert_type)
+                .cmp(&priority(&b.alert_type))
+                .then(b.count.cmp(&a.count))
+        });
+        self.total = self.groups.iter().map(|g| g.count).sum();
+    }
+}
+
+impl Tool for GetAlerts {
+    const NAME: &'static str = "get_alerts";
+    type Error = SenseiError;
+    type Args = GetAlertsArgs;
+    type Output = Alerts;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "get_alerts".to_string(),
+            description: "Get the player's active alerts (entity destroyed or under attack, turret out of ammo, no material for construction, train out of fuel, and others), grouped by surface and alert type with counts and sample positions, most urgent first.".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "surface": {
+                        "type": "string",
+                        "description": "Only alerts on this surface, e.g. 'nauvis', 'vulcanus', or a space platform (default: every surface)"
+                    }
+                }
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let lua = lua::alerts(args.surface.as_deref());
+        let json = execute_lua_json(&self.rcon, &lua).await?;
+        let mut result: Alerts = serde_json::from_str(&json)?;
+        result.sort();
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_alerts() {
+        let json = r#"{"groups":[
+            {"alert_type":"no_material_for_construction","surface":"nauvis","count":12,
+             "alerts":[{"name":"fast-inserter","x":10.5,"y":-3.5,"seconds_ago":4.2}]},
+            {"alert_type":"turret_out_of_ammo","surface":"nauvis","count":3,
+             "alerts":[{"name":"gun-turret","x":-120,"y":-340,"seconds_ago":65}]},
+            {"alert_type":"entity_destroyed","surface":"vulcanus","count":1,
+             "alerts":[{"name":"stone-wall","x":4,"y":90,"seconds_ago":12}]}
+        ]}"#;
+        let mut a: Alerts = serde_json::from_str(json).unwrap();
+        a.sort();
+        assert_eq!(a.total, 16);
+        assert_eq!(a.groups[0].alert_type, "entity_destroyed");
+        assert_eq!(a.groups[1].alert_type, "turret_out_of_ammo");
+        assert_eq!(a.groups[2].alerts[0].name.as_deref(), Some("fast-inserter"));
+    }
+
+    #[test]
+    fn test_parse_no_alerts() {
+        let mut a: Alerts = serde_json::from_str("{}").unwrap();
+        a.sort();
+        assert!(a.groups.is_empty());
+        assert_eq!(a.total, 0);
+    }
+
+    #[test]
+    fn test_unknown_types_sort_last_by_count() {
+        let json = r#"{"groups":[
+            {"alert_type":"modded_alert","surface":"nauvis","count":50},
+            {"alert_type":"custom","surface":"nauvis","count":1,
+             "alerts":[{"seconds_ago":0,"message":"Low on coal"}]},
+            {"alert_type":"other_modded_alert","surface":"nauvis","count":70}
+        ]}"#;
+        let mut a: Alerts = serde_json::from_str(json).unwrap();
+        a.sort();
+        assert_eq!(a.groups[0].alert_type, "custom");
+        assert_eq!(a.groups[1].alert_type, "other_modded_alert");
+        assert_eq!(
+            a.groups[0].alerts[0].message.as_deref(),
+            Some("Low on coal")
+        );
+    }
+}
@@ -1,20 +1,30 @@
-//! Tool for listing assembling machines and their current recipes.
+//! Tool for listing assembling machines, their current recipes, and why they are idle.
 //!
 //! Finds entities of `type="assembling-machine"` up to a configurable limit.
 //! For each machine, reports its prototype name, position, assigned recipe
-//! (if any), and effective crafting speed (accounting for modules/beacons).
+//! (if any), effective crafting speed (accounting for modules/beacons),
+//! `entity_status` (working, no_ingredients, full_output, no_power, ...),
+//! installed modules, the number of beacons in range, and the combined module
+//! effects so setups can be checked against the knowledge base's module math.
+//!
+//! In summary mode, every machine on the surface is grouped by recipe with
+//! status counts and the ingredients that starved machines are waiting on —
+//! the fastest way to find a bottleneck.
+
+use std::collections::BTreeMap;
 
 use rig::{completion::ToolDefinition, tool::Tool};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
+use super::{inventory::InventoryItem, surface_param};
 use crate::{
     error::SenseiError,
     lua,
     rcon_ext::{execute_lua_json, SharedRcon},
 };
 
-/// Lists assembling machines with their positions, recipes, and crafting speeds.
+/// Lists assembling machines with their positions, recipes, speeds, and status.
 pub struct GetAssemblers {
     pub(crate) rcon: SharedRcon,
 }
@@ -28,8 +38,12 @@ impl GetAssemblers {
 /// Arguments for [`GetAssemblers`]. All optional.
 #[derive(Debug, Deserialize)]
 pub struct GetAssemblersArgs {
-    /// Max machines to return. Defaults to 30 if omitted.
+    /// Max machines to return. Defaults to 30 if omitted. Ignored in summary mode.
     pub limit: Option<u32>,
+    /// Group every machine by recipe with status counts instead of listing machines.
+    pub summary: Option<bool>,
+    #[doc = surface_arg_doc!()]
+    pub surface: Option<String>,
 }
 
 /// A single assembling machine's state snapshot.
@@ -45,12 +59,59 @@ pub struct AssemblerInfo {
     pub recipe: Option<String>,
     /// Effective crafting speed (base speed * module bonuses).
     pub crafting_speed: f64,
+    /// `defines.entity_status` name (e.g. "working", "no_ingredients", "full_output").
+    pub status: Option<String>,
+    /// Modules installed in the machine.
+    #[serde(default)]
+    pub modules: Vec<InventoryItem>,
+    /// Number of beacons affecting the machine.
+    #[serde(default)]
+    pub beacons: u32,
+    /// Combined effects of modules and beacons.
+    #[serde(default)]
+    pub effects: ModuleEffects,
+    /// Built-in productivity bonus (research/prototype), separate from module effects.
+    #[serde(default)]
+    pub productivity_bonus: f64,
+}
+
+/// Combined module/beacon effects as fractions (0.5 = +50%, -0.15 = -15%).
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ModuleEffects {
+    #[serde(default)]
+    pub speed: f64,
+    #[serde(default)]
+    pub productivity: f64,
+    /// Energy consumption modifier.
+    #[serde(default)]
+    pub consumption: f64,
+    #[serde(default)]
+    pub pollution: f64,
+    #[serde(default)]
+    pub quality: f64,
+}
+
+/// Machines sharing one recipe, counted by status.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct RecipeStatusGroup {
+    /// The shared recipe, or `None` for machines without a recipe.
+    pub recipe: Option<String>,
+    /// Number of machines in this group.
+    pub machines: u32,
+    /// Machine count per `defines.entity_status` name.
+    pub statuses: BTreeMap<String, u32>,
+    /// For machines waiting on ingredients: ingredient name → machines short on it.
+    #[serde(default)]
+    pub missing_ingredients: BTreeMap<String, u32>,
 }
 
-/// Top-level response wrapper for the assemblers list.
+/// Top-level response: a machine list, or recipe groups in summary mode.
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct Assemblers {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub assemblers: Vec<AssemblerInfo>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<RecipeStatusGroup>,
 }
 
 impl Tool for GetAssemblers {
@@ -62,24 +123,31 @@ impl Tool for GetAssemblers {
     async fn definition(&self, _prompt: String) -> ToolDefinition {
         ToolDefinition {
             name: "get_assemblers".to_string(),
-            description:
-                "Get assembling machines on the map with their recipes and crafting speeds"
-                    .to_string(),
+            description: "Get assembling machines on the map with their recipes, crafting speeds, modules, beacon count, module effects (speed/productivity/consumption/pollution), and status (working, no_ingredients, full_output, no_power, low_power, ...). Use summary mode to group all machines by recipe with status counts and the ingredients starved machines are missing — best for finding bottlenecks.".to_string(),
             parameters: json!({
                 "type": "object",
                 "properties": {
                     "limit": {
                         "type": "integer",
-                        "description": "Maximum number of assemblers to return (default: 30)"
-                    }
+                        "description": "Maximum number of assemblers to return (default: 30, ignored in summary mode)"
+                    },
+                    "summary": {
+                        "type": "boolean",
+                        "description": "Group every assembler by recipe with status counts instead of listing machines (default: false)"
+                    },
+                    "surface": surface_param()
                 }
             }),
         }
     }
 
     async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
-        let limit = args.limit.unwrap_or(30);
-        let lua = lua::assemblers(limit);
+        let surface = args.surface.as_deref();
+        let lua = if args.summary.unwrap_or(false) {
+            lua::assembler_summary(surface)
+        } else {
+            lua::assemblers(args.limit.unwrap_or(30), surface)
+        };
         let json = execute_lua_json(&self.rcon, &lua).await?;
         Ok(serde_json::from_str(&json)?)
     }
@@ -92,8 +160,8 @@ mod tests {
     #[test]
     fn test_parse_assemblers() {
         let json = r#"{"assemblers":[
-            {"name":"assembling-machine-1","x":5.0,"y":10.0,"recipe":"iron-gear-wheel","crafting_speed":0.5},
-            {"name":"assembling-machine-2","x":8.0,"y":10.0,"recipe":null,"crafting_speed":0.75}
+            {"name":"assembling-machine-1","x":5.0,"y":10.0,"recipe":"iron-gear-wheel","crafting_speed":0.5,"status":"working"},
+            {"name":"assembling-machine-2","x":8.0,"y":10.0,"recipe":null,"crafting_speed":0.75,"status":"no_recipe"}
         ]}"#;
         let result: Assemblers = serde_json::from_str(json).unwrap();
         assert_eq!(result.assemblers.len(), 2);
@@ -101,13 +169,51 @@ mod tests {
             result.assemblers[0].recipe.as_deref(),
             Some("iron-gear-wheel")
         );
+        assert_eq!(result.assemblers[0].status.as_deref(), Some("working"));
         assert_eq!(result.assemblers[1].recipe, None);
+        assert!(result.assemblers[1].modules.is_empty());
+        assert_eq!(result.assemblers[1].effects, ModuleEffects::default());
+        assert!(result.groups.is_empty());
+    }
+
+    #[test]
+    fn test_parse_modules_and_beacons() {
+        let json = r#"{"assemblers":[
+            {"name":"assembling-machine-3","x":1.5,"y":2.5,"recipe":"electronic-circuit","crafting_speed":4.6,"status":"working",
+             "modules":[{"name":"productivity-module-3","count":4}],"beacons":8,
+             "effects":{"speed":2.68,"productivity":0.4,"consumption":6.2,"pollution":0.4,"quality":0},
+             "productivity_bonus":0.1}
+        ]}"#;
+        let result: Assemblers = serde_json::from_str(json).unwrap();
+        let machine = &result.assemblers[0];
+        assert_eq!(machine.modules[0].name, "productivity-module-3");
+        assert_eq!(machine.modules[0].count, 4);
+        assert_eq!(machine.beacons, 8);
+        assert_eq!(machine.effects.productivity, 0.4);
+        assert_eq!(machine.productivity_bonus, 0.1);
     }
 
     #[test]
     fn test_parse_no_assemblers() {
-        let json = r#"{"assemblers":[]}"#;
+        let result: Assemblers = serde_json::from_str("{}").unwrap();
+        assert!(result.assemblers.is_empty());
+        assert!(result.groups.is_empty());
+    }
+
+    #[test]
+    fn test_parse_summary_groups() {
+        let json = r#"{"groups":[
+            {"recipe":"iron-gear-wheel","machines":12,
+             "statuses":{"working":6,"no_ingredients":4,"full_output":2},
+             "missing_ingredients":{"iron-plate":4}},
+            {"machines":1,"statuses":{"no_recipe":1}}
+        ]}"#;
         let result: Assemblers = serde_json::from_str(json).unwrap();
         assert!(result.assemblers.is_empty());
+        let gears = &result.groups[0];
+        assert_eq!(gears.statuses["no_ingredients"], 4);
+        assert_eq!(gears.missing_ingredients["iron-plate"], 4);
+        assert_eq!(result.groups[1].recipe, None);
+        assert!(result.groups[1].missing_ingredients.is_empty());
     }
 }
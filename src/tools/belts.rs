@@ -0,0 +1,367 @@
+//! Tool for analyzing belt saturation and lane contents.
+//!
+//! Reads every transport line of each belt, underground, and splitter in an
+//! area — or along a belt path followed downstream from a starting belt — and
+//! reports the item count and item types on each line. Odd-numbered lines are
+//! left lanes and even-numbered lines right lanes, so undergrounds (4 lines) and
+//! splitters (8 lines) count fully toward their side.
+//!
+//! Lane totals are computed on the Rust side. Capacity uses the knowledge base
+//! belt physics (4 items per lane per straight tile; 295/64 and 106/64 on the
+//! outer and inner lane of a turn), and flow is compared against the belt
+//! throughput table (7.5/15/22.5/30 items/s per lane for yellow/red/blue/turbo).
+
+use std::cmp::Reverse;
+
+use rig::{completion::ToolDefinition, tool::Tool};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::{inventory::InventoryItem, surface_param};
+use crate::{
+    error::SenseiError,
+    lua,
+    rcon_ext::{execute_lua_json, SharedRcon},
+};
+
+/// Items per lane on one straight belt tile (256 positions / 64-position spacing).
+const ITEMS_PER_LANE_TILE: f64 = 4.0;
+/// Outer lane of a turn: 295 positions.
+const OUTER_TURN_ITEMS: f64 = 295.0 / 64.0;
+/// Inner lane of a turn: 106 positions.
+const INNER_TURN_ITEMS: f64 = 106.0 / 64.0;
+
+/// Max items/second per lane for a belt tier, from the knowledge base throughput table.
+/// Undergrounds and splitters share their belt's tier.
+fn lane_throughput(name: &str) -> Option<f64> {
+    let tier = name
+        .trim_end_matches("transport-belt")
+        .trim_end_matches("underground-belt")
+        .trim_end_matches("splitter");
+    match tier {
+        "" => Some(7.5),
+        "fast-" => Some(15.0),
+        "express-" => Some(22.5),
+        "turbo-" => Some(30.0),
+        _ => None,
+    }
+}
+
+/// Reads lane contents of belts in an area or along a belt path.
+pub struct GetBelts {
+    pub(crate) rcon: SharedRcon,
+}
+
+impl GetBelts {
+    pub const fn new(rcon: SharedRcon) -> Self {
+        Self { rcon }
+    }
+}
+
+/// Arguments for [`GetBelts`]. All optional.
+#[derive(Debug, Deserialize)]
+pub struct GetBeltsArgs {
+    /// Center (or, with `follow`, starting belt) x coordinate. Defaults to the player.
+    pub x: Option<f64>,
+    /// Center (or starting belt) y coordinate. Defaults to the player.
+    pub y: Option<f64>,
+    /// Area mode search radius in tiles. Defaults to 10.
+    pub radius: Option<f64>,
+    /// Follow the belt downstream from x/y instead of scanning an area.
+    pub follow: Option<bool>,
+    /// Max belts to read. Defaults to 100.
+    pub limit: Option<u32>,
+    #[doc = surface_arg_doc!()]
+    pub surface: Option<String>,
+}
+
+/// One transport line of a belt.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct LaneContents {
+    /// Items currently on the lane.
+    pub count: u64,
+    #[serde(default)]
+    pub items: Vec<InventoryItem>,
+}
+
+/// A single belt entity and its transport lines.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct BeltInfo {
+    /// Entity prototype name (e.g. "fast-transport-belt", "express-splitter").
+    pub name: String,
+    pub x: f64,
+    pub y: f64,
+    /// "straight", "left", or "right" (turn direction).
+    pub shape: String,
+    /// Belt speed in tiles per tick.
+    pub speed: f64,
+    /// Transport lines in index order: left, right, left, right, ...
+    /// Belts have 2, undergrounds 4, and splitters 8.
+    pub lanes: Vec<LaneContents>,
+}
+
+impl BeltInfo {
+    /// Item capacity of lane `side` (0 = left, 1 = right) on this entity.
+    /// A splitter spans two belts, so each side holds two lanes' worth.
+    fn lane_capacity(&self, side: usize) -> f64 {
+        let per_belt = match (self.shape.as_str(), side) {
+            ("left", 0) | ("right", 1) => INNER_TURN_ITEMS,
+            ("left" | "right", _) => OUTER_TURN_ITEMS,
+            _ => ITEMS_PER_LANE_TILE,
+        };
+        if self.name.ends_with("splitter") {
+            per_belt * 2.0
+        } else {
+            per_belt
+        }
+    }
+
+    /// Transport lines on lane `side` (0 = left, 1 = right).
+    fn side_lines(&self, side: usize) -> impl Iterator<Item = &LaneContents> {
+        self.lanes.iter().skip(side).step_by(2)
+    }
+
+    fn lane_throughput(&self) -> f64 {
+        lane_throughput(&self.name).unwrap_or(self.speed * 60.0 * ITEMS_PER_LANE_TILE)
+    }
+}
+
+/// Totals for one lane across every belt read.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct LaneSummary {
+    /// "left" or "right" (relative to belt direction).
+    pub lane: String,
+    pub items: u64,
+    /// Items the lane could hold across these belts when fully compressed.
+    pub capacity: f64,
+    /// `items / capacity`, 0.0–1.0.
+    pub fill_ratio: f64,
+    /// Distinct item types on the lane, most common first.
+    pub item_types: Vec<String>,
+    /// More than one item type on the lane.
+    pub mixed: bool,
+    /// Throughput of the slowest belt tier read, items/s.
+    pub max_per_second: f64,
+    /// Flow if the lane is moving at its current density, items/s. A backed-up lane
+    /// also reads as full.
+    pub estimated_per_second: f64,
+    /// "empty", "partial", or "saturated".
+    pub status: String,
+}
+
+/// Sum the left and right lanes over `belts` and rate them against the throughput table.
+fn summarize_lanes(belts: &[BeltInfo]) -> Vec<LaneSummary> {
+    let max_per_second = belts
+        .iter()
+        .map(BeltInfo::lane_throughput)
+        .reduce(f64::min)
+        .unwrap_or(0.0);
+    ["left", "right"]
+        .iter()
+        .enumerate()
+        .map(|(side, lane)| {
+            let mut items = 0;
+            let mut capacity = 0.0;
+            let mut types: Vec<InventoryItem> = Vec::new();
+            for belt in belts {
+                capacity += belt.lane_capacity(side);
+                for contents in belt.side_lines(side) {
+                    items += contents.count;
+                    for it in &contents.items {
+                        match types.iter_mut().find(|t| t.name == it.name) {
+                            Some(t) => t.count += it.count,
+                            None => types.push(InventoryItem {
+                                name: it.name.clone(),
+                                count: it.count,
+                            }),
+                        }
+                    }
+                }
+            }
+            types.sort_by_key(|t| Reverse(t.count));
+            let fill_ratio = if capacity > 0.0 {
+                (items as f64 / capacity).min(1.0)
+            } else {
+                0.0
+            };
+            let status = if items == 0 {
+                "empty"
+            } else if fill_ratio >= 0.95 {
+                "saturated"
+            } else {
+                "partial"
+            };
+            LaneSummary {
+                lane: (*lane).to_string(),
+                items,
+                capacity,
+                fill_ratio,
+                mixed: types.len() > 1,
+                item_types: types.into_iter().map(|t| t.name).collect(),
+                max_per_second,
+                estimated_per_second: fill_ratio * max_per_second,
+                status: status.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Top-level response: per-belt lanes plus totals per lane.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct Belts {
+    #[serde(default)]
+    pub belts: Vec<BeltInfo>,
+    #[serde(default)]
+    pub lanes: Vec<LaneSummary>,
+}
+
+impl Tool for GetBelts {
+    const NAME: &'static str = "get_belts";
+    type Error = SenseiError;
+    type Args = GetBeltsArgs;
+    type Output = Belts;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "get_belts".to_string(),
+            description: "Read belt lanes in an area, or follow a belt downstream from a position. Reports items and item types on each lane per belt, and per-lane totals: fill ratio, mixed items, max throughput of the belt tier, estimated flow, and whether the lane is empty, partial, or saturated.".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "x": {
+                        "type": "number",
+                        "description": "Center x, or the starting belt's x when following (default: player position)"
+                    },
+                    "y": {
+                        "type": "number",
+                        "description": "Center y, or the starting belt's y when following (default: player position)"
+                    },
+                    "radius": {
+                        "type": "number",
+                        "description": "Area search radius in tiles (default: 10)"
+                    },
+                    "follow": {
+                        "type": "boolean",
+                        "description": "Follow the belt path downstream from x/y instead of scanning an area (default: false)"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of belts to read (default: 100)"
+                    },
+                    "surface": surface_param()
+                }
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let center = args.x.zip(args.y);
+        let lua = lua::belts(
+            center,
+            args.radius.unwrap_or(10.0),
+            args.follow.unwrap_or(false),
+            args.limit.unwrap_or(100),
+            args.surface.as_deref(),
+        );
+        let json = execute_lua_json(&self.rcon, &lua).await?;
+        let mut result: Belts = serde_json::from_str(&json)?;
+        result.lanes = summarize_lanes(&result.belts);
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn belts(json: &str) -> Vec<BeltInfo> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_parse_belts() {
+        let json = r#"{"belts":[
+            {"name":"transport-belt","x":0.5,"y":0.5,"shape":"straight","speed":0.03125,
+             "lanes":[{"count":4,"items":[{"name":"iron-plate","count":4}]},{"count":0}]}
+        ]}"#;
+        let result: Belts = serde_json::from_str(json).unwrap();
+        assert_eq!(result.belts[0].lanes[0].items[0].name, "iron-plate");
+        assert!(result.belts[0].lanes[1].items.is_empty());
+    }
+
+    #[test]
+    fn test_parse_no_belts() {
+        let mut result: Belts = serde_json::from_str("{}").unwrap();
+        result.lanes = summarize_lanes(&result.belts);
+        assert!(result.belts.is_empty());
+        assert_eq!(result.lanes[0].status, "empty");
+    }
+
+    #[test]
+    fn test_lane_throughput_table() {
+        assert_eq!(lane_throughput("transport-belt"), Some(7.5));
+        assert_eq!(lane_throughput("fast-underground-belt"), Some(15.0));
+        assert_eq!(lane_throughput("express-splitter"), Some(22.5));
+        assert_eq!(lane_throughput("turbo-transport-belt"), Some(30.0));
+        assert_eq!(lane_throughput("modded-belt"), None);
+    }
+
+    #[test]
+    fn test_half_saturated_belt() {
+        // Two red belt tiles: left lane full of iron, right lane empty
+        let b = belts(
+            r#"[{"name":"fast-transport-belt","x":0.5,"y":0.5,"shape":"straight","speed":0.0625,
+                 "lanes":[{"count":4,"items":[{"name":"iron-plate","count":4}]},{"count":0}]},
+                {"name":"fast-transport-belt","x":0.5,"y":1.5,"shape":"straight","speed":0.0625,
+                 "lanes":[{"count":4,"items":[{"name":"iron-plate","count":4}]},{"count":0}]}]"#,
+        );
+        let lanes = summarize_lanes(&b);
+        assert_eq!(lanes[0].status, "saturated");
+        assert_eq!(lanes[0].estimated_per_second, 15.0);
+        assert!(!lanes[0].mixed);
+        assert_eq!(lanes[1].status, "empty");
+        assert_eq!(lanes[1].item_types, Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_mixed_lane_and_turn_capacity() {
+        // Left turn: left lane is the inner lane and holds 106/64 items
+        let b = belts(
+            r#"[{"name":"transport-belt","x":0.5,"y":0.5,"shape":"left","speed":0.03125,
+                 "lanes":[{"count":1,"items":[{"name":"coal","count":1}]},
+                          {"count":2,"items":[{"name":"stone","count":1},{"name":"coal","count":1}]}]}]"#,
+        );
+        assert_eq!(b[0].lane_capacity(0), INNER_TURN_ITEMS);
+        assert_eq!(b[0].lane_capacity(1), OUTER_TURN_ITEMS);
+        let lanes = summarize_lanes(&b);
+        assert!(lanes[1].mixed);
+        assert_eq!(lanes[1].status, "partial");
+    }
+
+    #[test]
+    fn test_splitter_counts_every_line() {
+        // Lines 1-4 feed in, 5-8 lead out; odd lines are left lanes
+        let b = belts(
+            r#"[{"name":"splitter","x":1,"y":0.5,"shape":"straight","speed":0.03125,
+                 "lanes":[{"count":2,"items":[{"name":"iron-plate","count":2}]},{"count":0},
+                          {"count":2,"items":[{"name":"iron-plate","count":2}]},{"count":0},
+                          {"count":2,"items":[{"name":"iron-plate","count":2}]},{"count":1,"items":[{"name":"coal","count":1}]},
+                          {"count":2,"items":[{"name":"iron-plate","count":2}]},{"count":0}]}]"#,
+        );
+        assert_eq!(b[0].lane_capacity(0), 2.0 * ITEMS_PER_LANE_TILE);
+        let lanes = summarize_lanes(&b);
+        assert_eq!(lanes[0].items, 8);
+        assert_eq!(lanes[0].status, "saturated");
+        assert_eq!(lanes[1].items, 1);
+        assert_eq!(lanes[1].item_types, vec!["coal"]);
+    }
+
+    #[test]
+    fn test_mixed_tiers_use_slowest_belt() {
+        let b = belts(
+            r#"[{"name":"express-transport-belt","x":0.5,"y":0.5,"shape":"straight","speed":0.09375,"lanes":[{"count":0},{"count":0}]},
+                {"name":"transport-belt","x":0.5,"y":1.5,"shape":"straight","speed":0.03125,"lanes":[{"count":0},{"count":0}]}]"#,
+        );
+        assert_eq!(summarize_lanes(&b)[0].max_per_second, 7.5);
+    }
+}
@@ -0,0 +1,186 @@
+//! Tool for finding out why blueprints aren't building.
+//!
+//! Counts entity ghosts, tile ghosts, and entities marked for deconstruction or
+//! upgrade — near the player with a radius, or on the whole surface without one.
+//! The items the ghosts and upgrades need are compared against the stock of the
+//! logistic networks covering them.
+//!
+//! Blockers are derived on the Rust side: items the networks are short of, ghosts
+//! outside any construction area, and networks without a free construction robot.
+
+use rig::{completion::ToolDefinition, tool::Tool};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::surface_param;
+use crate::{
+    error::SenseiError,
+    lua,
+    rcon_ext::{execute_lua_json, SharedRcon},
+};
+
+/// Summarizes ghosts and deconstruction/upgrade marks with the items they need.
+pub struct GetConstructionBacklog {
+    pub(crate) rcon: SharedRcon,
+}
+
+impl GetConstructionBacklog {
+    pub const fn new(rcon: SharedRcon) -> Self {
+        Self { rcon }
+    }
+}
+
+/// Arguments for [`GetConstructionBacklog`]. All optional.
+#[derive(Debug, Deserialize)]
+pub struct GetConstructionBacklogArgs {
+    /// Search radius in tiles around the player (or the map origin on another surface).
+    /// Defaults to the whole surface.
+    pub radius: Option<f64>,
+    #[doc = surface_arg_doc!()]
+    pub surface: Option<String>,
+}
+
+/// An item needed by ghosts or upgrades, against what the networks hold.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct NeededItem {
+    pub name: String,
+    /// Items needed to place every ghost/upgrade.
+    pub needed: u64,
+    /// Items stored in the logistic networks covering the ghosts.
+    pub available: u64,
+    /// Shortfall the player must craft or deliver.
+    #[serde(default)]
+    pub missing: u64,
+}
+
+/// Construction work waiting in an area.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ConstructionBacklog {
+    pub entity_ghosts: u32,
+    pub tile_ghosts: u32,
+    /// Entities marked for upgrade.
+    pub to_upgrade: u32,
+    /// Entities (including trees and rocks) marked for deconstruction.
+    pub to_deconstruct: u32,
+    /// Ghosts and upgrades outside every construction area — robots can't reach them.
+    pub out_of_coverage: u32,
+    /// Idle construction robots in the networks covering the ghosts.
+    pub available_construction_robots: u32,
+    #[serde(default)]
+    pub items: Vec<NeededItem>,
+    /// Plain-language reasons the backlog is stalled.
+    #[serde(default)]
+    pub blockers: Vec<String>,
+}
+
+impl ConstructionBacklog {
+    /// Fill each item's shortfall and list what stops robots from building.
+    fn diagnose(&mut self) {
+        for item in &mut self.items {
+            item.missing = item.needed.saturating_sub(item.available);
+        }
+        self.items
+            .sort_by(|a, b| b.missing.cmp(&a.missing).then(b.needed.cmp(&a.needed)));
+
+        let mut blockers = Vec::new();
+        let short: Vec<&str> = self
+            .items
+            .iter()
+            .filter(|i| i.missing > 0)
+            .map(|i| i.name.as_str())
+            .collect();
+        if !short.is_empty() {
+            blockers.push(format!("network is missing items: {}", short.join(", ")));
+        }
+        if self.out_of_coverage > 0 {
+            blockers.push(format!(
+                "{} ghosts/upgrades are outside roboport coverage",
+                self.out_of_coverage
+            ));
+        }
+        let pending = self.entity_ghosts + self.tile_ghosts + self.to_upgrade;
+        if pending > self.out_of_coverage && self.available_construction_robots == 0 {
+            blockers.push("no idle construction robots in the covering networks".to_string());
+        }
+        self.blockers = blockers;
+    }
+}
+
+impl Tool for GetConstructionBacklog {
+    const NAME: &'static str = "get_construction_backlog";
+    type Error = SenseiError;
+    type Args = GetConstructionBacklogArgs;
+    type Output = ConstructionBacklog;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "get_construction_backlog".to_string(),
+            description: "Count entity ghosts, tile ghosts, and entities marked for deconstruction or upgrade, near the player or on the whole surface. Lists the items the ghosts need versus what the covering logistic networks hold, and the blockers (missing items, no roboport coverage, no idle construction robots).".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "radius": {
+                        "type": "number",
+                        "description": "Search radius in tiles around the player (default: the whole surface)"
+                    },
+                    "surface": surface_param()
+                }
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let lua = lua::construction(args.radius, args.surface.as_deref());
+        let json = execute_lua_json(&self.rcon, &lua).await?;
+        let mut result: ConstructionBacklog = serde_json::from_str(&json)?;
+        result.diagnose();
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backlog(json: &str) -> ConstructionBacklog {
+        let mut b: ConstructionBacklog = serde_json::from_str(json).unwrap();
+        b.diagnose();
+        b
+    }
+
+    #[test]
+    fn test_parse_empty_backlog() {
+        let b = backlog(
+            r#"{"entity_ghosts":0,"tile_ghosts":0,"to_upgrade":0,"to_deconstruct":0,
+                "out_of_coverage":0,"available_construction_robots":0}"#,
+        );
+        assert!(b.items.is_empty());
+        assert!(b.blockers.is_empty());
+    }
+
+    #[test]
+    fn test_missing_items_block_construction() {
+        let b = backlog(
+            r#"{"entity_ghosts":40,"tile_ghosts":0,"to_upgrade":0,"to_deconstruct":3,
+                "out_of_coverage":0,"available_construction_robots":25,
+                "items":[{"name":"transport-belt","needed":30,"available":200},
+                         {"name":"fast-inserter","needed":10,"available":4}]}"#,
+        );
+        assert_eq!(b.items[0].name, "fast-inserter");
+        assert_eq!(b.items[0].missing, 6);
+        assert_eq!(b.items[1].missing, 0);
+        assert_eq!(b.blockers, vec!["network is missing items: fast-inserter"]);
+    }
+
+    #[test]
+    fn test_coverage_and_robot_blockers() {
+        let b = backlog(
+            r#"{"entity_ghosts":12,"tile_ghosts":0,"to_upgrade":0,"to_deconstruct":0,
+                "out_of_coverage":5,"available_construction_robots":0,
+                "items":[{"name":"stone-wall","needed":12,"available":500}]}"#,
+        );
+        assert_eq!(b.blockers.len(), 2);
+        assert!(b.blockers[0].contains("outside roboport coverage"));
+        assert!(b.blockers[1].contains("no idle construction robots"));
+    }
+}
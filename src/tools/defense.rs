@@ -0,0 +1,302 @@
+//! Tool for judging whether the base can hold off biters.
+//!
+//! Within a radius of the player it finds enemy spawners and worms that sit close
+//! to the force's buildings, the nearest turrets (plus the full turret count) with
+//! their ammo, fluid, or electric buffer, wall coverage around turrets, and recent
+//! damage from the player's
+//! `entity_under_attack` / `entity_destroyed` alerts. The enemy evolution factor is
+//! included so threats can be judged against what will attack.
+//!
+//! Turret readiness and the per-direction summary ("northern gun turrets have 30
+//! magazines left") are computed on the Rust side.
+
+use std::collections::BTreeMap;
+
+use rig::{completion::ToolDefinition, tool::Tool};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::{inventory::InventoryItem, surface_param};
+use crate::{
+    error::SenseiError,
+    lua,
+    rcon_ext::{execute_lua_json, SharedRcon},
+};
+
+/// Ammo-fed turrets with fewer rounds than this are reported as low.
+const LOW_AMMO: u64 = 10;
+/// Electric turrets below this buffer fraction cannot keep firing.
+const LOW_ENERGY_RATIO: f64 = 0.1;
+
+/// Reports nearby enemy bases, turret readiness, walls, and recent damage.
+pub struct GetDefense {
+    pub(crate) rcon: SharedRcon,
+}
+
+impl GetDefense {
+    pub const fn new(rcon: SharedRcon) -> Self {
+        Self { rcon }
+    }
+}
+
+/// Arguments for [`GetDefense`]. All optional.
+#[derive(Debug, Deserialize)]
+pub struct GetDefenseArgs {
+    /// Search radius in tiles around the player (or the map origin on another surface).
+    /// Defaults to 500.
+    pub radius: Option<f64>,
+    /// Max distance in tiles from the force's buildings for a nest to count as a threat.
+    /// Defaults to 100.
+    pub distance: Option<f64>,
+    #[doc = surface_arg_doc!()]
+    pub surface: Option<String>,
+}
+
+/// An enemy spawner or worm near the base.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct EnemyThreat {
+    /// Entity prototype name (e.g. "biter-spawner", "medium-worm-turret").
+    pub name: String,
+    pub x: f64,
+    pub y: f64,
+    /// Distance from the player in tiles.
+    pub distance: f64,
+    /// Compass direction from the player (e.g. "north").
+    pub direction: String,
+}
+
+/// Fluid loaded in a flamethrower turret.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct TurretFluid {
+    pub name: String,
+    pub amount: f64,
+}
+
+/// A single turret's readiness snapshot.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct TurretInfo {
+    /// Entity prototype name (e.g. "gun-turret", "laser-turret").
+    pub name: String,
+    /// Entity type: "ammo-turret", "fluid-turret", "electric-turret", or "artillery-turret".
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub x: f64,
+    pub y: f64,
+    pub distance: f64,
+    pub direction: String,
+    pub status: Option<String>,
+    /// Health as a fraction of max health.
+    pub health_ratio: f64,
+    /// Whether a wall or gate stands within 6 tiles.
+    pub walled: bool,
+    /// Loaded ammo (ammo and artillery turrets).
+    #[serde(default)]
+    pub ammo: Vec<InventoryItem>,
+    /// Loaded fluid (flamethrower turrets).
+    #[serde(default)]
+    pub fluid: Option<TurretFluid>,
+    /// Energy buffer fill, 0.0–1.0 (electric turrets).
+    #[serde(default)]
+    pub energy_ratio: Option<f64>,
+    /// "ready", "low_ammo", "no_ammo", "no_fluid", or "no_power".
+    #[serde(default)]
+    pub readiness: String,
+}
+
+impl TurretInfo {
+    fn ammo_count(&self) -> u64 {
+        self.ammo.iter().map(|a| a.count).sum()
+    }
+
+    fn readiness(&self) -> &'static str {
+        match self.kind.as_str() {
+            "ammo-turret" | "artillery-turret" => match self.ammo_count() {
+                0 => "no_ammo",
+                n if n < LOW_AMMO => "low_ammo",
+                _ => "ready",
+            },
+            "fluid-turret" if self.fluid.as_ref().is_none_or(|f| f.amount <= 0.0) => "no_fluid",
+            "electric-turret" if self.energy_ratio.unwrap_or(0.0) < LOW_ENERGY_RATIO => "no_power",
+            _ => "ready",
+        }
+    }
+}
+
+/// A recent damage or destruction alert.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct DamageAlert {
+    /// Entity prototype name that was hit or destroyed.
+    pub name: String,
+    pub x: Option<f64>,
+    pub y: Option<f64>,
+    pub seconds_ago: f64,
+}
+
+/// Turrets facing one compass direction from the player.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct DirectionDefense {
+    pub turrets: u32,
+    /// Turrets with readiness "ready".
+    pub ready: u32,
+    /// Turrets without a wall nearby.
+    pub unwalled: u32,
+    /// Ammo items loaded across the direction's ammo turrets.
+    pub ammo: u64,
+}
+
+/// Top-level defense report.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct Defense {
+    /// Enemy evolution factor on the surface.
+    pub evolution: f64,
+    /// Spawners within `distance` of the force's buildings.
+    pub spawners: u32,
+    /// Worms within `distance` of the force's buildings.
+    pub worms: u32,
+    /// The nearest 30 of those spawners and worms.
+    #[serde(default)]
+    pub threats: Vec<EnemyThreat>,
+    /// The nearest 100 turrets in the search radius.
+    #[serde(default)]
+    pub turrets: Vec<TurretInfo>,
+    /// Every turret in the search radius, listed or not.
+    pub turret_count: u32,
+    /// Wall and gate entities in the search radius.
+    pub walls: u32,
+    #[serde(default)]
+    pub under_attack: Vec<DamageAlert>,
+    #[serde(default)]
+    pub destroyed: Vec<DamageAlert>,
+    /// Turret summary per compass direction, over the listed turrets.
+    #[serde(default)]
+    pub by_direction: BTreeMap<String, DirectionDefense>,
+}
+
+impl Defense {
+    /// Rate each turret and roll them up per direction.
+    fn analyze(&mut self) {
+        self.by_direction = BTreeMap::new();
+        for turret in &mut self.turrets {
+            turret.readiness = turret.readiness().to_string();
+            let group =
+                self.by_direction
+                    .entry(turret.direction.clone())
+                    .or_insert(DirectionDefense {
+                        turrets: 0,
+                        ready: 0,
+                        unwalled: 0,
+                        ammo: 0,
+                    });
+            group.turrets += 1;
+            group.ready += u32::from(turret.readiness == "ready");
+            group.unwalled += u32::from(!turret.walled);
+            group.ammo += turret.ammo_count();
+        }
+        self.threats
+            .sort_by(|a, b| a.distance.total_cmp(&b.distance));
+    }
+}
+
+impl Tool for GetDefense {
+    const NAME: &'static str = "get_defense";
+    type Error = SenseiError;
+    type Args = GetDefenseArgs;
+    type Output = Defense;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "get_defense".to_string(),
+            description: "Check base defenses: enemy spawners and worms near the player's buildings (with direction and distance), turrets with ammo/fluid/power readiness and wall coverage, a per-direction turret summary, recently attacked and destroyed entities, and the enemy evolution factor.".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "radius": {
+                        "type": "number",
+                        "description": "Search radius in tiles around the player (default: 500)"
+                    },
+                    "distance": {
+                        "type": "number",
+                        "description": "Max distance from the player's buildings for a nest to count as a threat (default: 100)"
+                    },
+                    "surface": surface_param()
+                }
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let lua = lua::defense(
+            args.radius.unwrap_or(500.0),
+            args.distance.unwrap_or(100.0),
+            args.surface.as_deref(),
+        );
+        let json = execute_lua_json(&self.rcon, &lua).await?;
+        let mut result: Defense = serde_json::from_str(&json)?;
+        result.analyze();
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn turret(json: &str) -> TurretInfo {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_parse_defense() {
+        let json = r#"{"evolution":0.42,"spawners":2,"worms":1,
+            "threats":[{"name":"biter-spawner","x":0,"y":-300,"distance":300,"direction":"north"},
+                       {"name":"medium-worm-turret","x":0,"y":-250,"distance":250,"direction":"north"}],
+            "turrets":[
+              {"name":"gun-turret","type":"ammo-turret","x":0,"y":-100,"distance":100,"direction":"north",
+               "status":"working","health_ratio":1,"walled":true,"ammo":[{"name":"firearm-magazine","count":3}]},
+              {"name":"gun-turret","type":"ammo-turret","x":5,"y":-100,"distance":100,"direction":"north",
+               "status":"working","health_ratio":0.4,"walled":false,"ammo":[{"name":"piercing-rounds-magazine","count":50}]}
+            ],
+            "turret_count":2,"walls":120,
+            "destroyed":[{"name":"stone-wall","x":2,"y":-104,"seconds_ago":42.5}]}"#;
+        let mut d: Defense = serde_json::from_str(json).unwrap();
+        d.analyze();
+        assert_eq!(d.threats[0].name, "medium-worm-turret");
+        assert_eq!(d.turrets[0].readiness, "low_ammo");
+        let north = &d.by_direction["north"];
+        assert_eq!(north.turrets, 2);
+        assert_eq!(north.ready, 1);
+        assert_eq!(north.unwalled, 1);
+        assert_eq!(north.ammo, 53);
+        assert!(d.under_attack.is_empty());
+        assert_eq!(d.destroyed[0].name, "stone-wall");
+    }
+
+    #[test]
+    fn test_turret_readiness() {
+        let empty = turret(
+            r#"{"name":"gun-turret","type":"ammo-turret","x":0,"y":0,"distance":0,"direction":"north","health_ratio":1,"walled":false}"#,
+        );
+        assert_eq!(empty.readiness(), "no_ammo");
+        let flamer = turret(
+            r#"{"name":"flamethrower-turret","type":"fluid-turret","x":0,"y":0,"distance":0,"direction":"east","health_ratio":1,"walled":true}"#,
+        );
+        assert_eq!(flamer.readiness(), "no_fluid");
+        let laser = turret(
+            r#"{"name":"laser-turret","type":"electric-turret","x":0,"y":0,"distance":0,"direction":"south","health_ratio":1,"walled":true,"energy_ratio":0.02}"#,
+        );
+        assert_eq!(laser.readiness(), "no_power");
+        let charged = turret(
+            r#"{"name":"laser-turret","type":"electric-turret","x":0,"y":0,"distance":0,"direction":"south","health_ratio":1,"walled":true,"energy_ratio":1}"#,
+        );
+        assert_eq!(charged.readiness(), "ready");
+    }
+
+    #[test]
+    fn test_parse_quiet_surface() {
+        let json = r#"{"evolution":0,"spawners":0,"worms":0,"turret_count":0,"walls":0}"#;
+        let mut d: Defense = serde_json::from_str(json).unwrap();
+        d.analyze();
+        assert!(d.turrets.is_empty());
+        assert!(d.by_direction.is_empty());
+    }
+}
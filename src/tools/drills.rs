@@ -0,0 +1,199 @@
+//! Tool for inspecting mining drills and estimating when ore patches run out.
+//!
+//! Finds the force's `type="mining-drill"` entities (including pumpjacks) and
+//! reports each drill's `mining_target`, the resource amount left under it,
+//! status (working, waiting_for_space_in_destination, no_power,
+//! no_minable_resources, ...), and effective mining speed.
+//!
+//! Drills are also grouped by the resource they mine. Each group's rates use
+//! the knowledge base mining formula (`mining_speed / mining_time`, times
+//! `1 + productivity` for output and the drill's resource drain for depletion),
+//! and the time to depletion is estimated on the Rust side.
+
+use std::collections::BTreeMap;
+
+use rig::{completion::ToolDefinition, tool::Tool};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::surface_param;
+use crate::{
+    error::SenseiError,
+    lua,
+    rcon_ext::{execute_lua_json, SharedRcon},
+};
+
+/// Lists mining drills and summarizes them per mined resource.
+pub struct GetMiningDrills {
+    pub(crate) rcon: SharedRcon,
+}
+
+impl GetMiningDrills {
+    pub const fn new(rcon: SharedRcon) -> Self {
+        Self { rcon }
+    }
+}
+
+/// Arguments for [`GetMiningDrills`]. All optional.
+#[derive(Debug, Deserialize)]
+pub struct GetMiningDrillsArgs {
+    /// Max drills to list individually. Defaults to 30. Groups always cover every drill.
+    pub limit: Option<u32>,
+    #[doc = surface_arg_doc!()]
+    pub surface: Option<String>,
+}
+
+/// A single mining drill's state snapshot.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct DrillInfo {
+    /// Entity prototype name (e.g. "electric-mining-drill", "pumpjack").
+    pub name: String,
+    /// World x coordinate.
+    pub x: f64,
+    /// World y coordinate.
+    pub y: f64,
+    /// Resource being mined, or `None` if the drill has nothing left to mine.
+    pub resource: Option<String>,
+    /// Resource amount remaining in the drill's mining area.
+    pub amount: Option<f64>,
+    /// `defines.entity_status` name (e.g. "working", "no_minable_resources").
+    pub status: Option<String>,
+    /// Effective mining speed including module/beacon speed effects.
+    pub mining_speed: f64,
+}
+
+/// All drills mining one resource.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct DrillGroup {
+    /// Resource prototype name, or `None` for drills without a mining target.
+    pub resource: Option<String>,
+    /// Number of drills in this group.
+    pub drills: u32,
+    /// Drill count per `defines.entity_status` name.
+    pub statuses: BTreeMap<String, u32>,
+    /// Resource left under the group's drills, counting overlapping tiles once.
+    pub remaining_amount: f64,
+    /// Infinite resources (e.g. crude oil) never deplete.
+    pub infinite: bool,
+    /// Resource removed from the ground per second with every drill running.
+    pub extraction_per_second: f64,
+    /// Items (or fluid units) produced per second, including productivity.
+    pub output_per_second: f64,
+    /// Estimated minutes until the area under these drills is exhausted, if it depletes.
+    #[serde(default)]
+    pub minutes_to_depletion: Option<f64>,
+}
+
+impl DrillGroup {
+    /// Minutes until `remaining_amount` is mined out at `extraction_per_second`.
+    fn estimate_depletion(&self) -> Option<f64> {
+        if self.infinite || self.resource.is_none() || self.extraction_per_second <= 0.0 {
+            return None;
+        }
+        Some(self.remaining_amount / self.extraction_per_second / 60.0)
+    }
+}
+
+/// Top-level response: individual drills plus per-resource groups.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct MiningDrills {
+    #[serde(default)]
+    pub drills: Vec<DrillInfo>,
+    #[serde(default)]
+    pub groups: Vec<DrillGroup>,
+}
+
+impl Tool for GetMiningDrills {
+    const NAME: &'static str = "get_mining_drills";
+    type Error = SenseiError;
+    type Args = GetMiningDrillsArgs;
+    type Output = MiningDrills;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "get_mining_drills".to_string(),
+            description: "Get mining drills and pumpjacks with their mined resource, amount remaining under each drill, status (working, output blocked, no power, depleted), and mining speed. Also groups drills by resource with total output per second and estimated minutes until depletion.".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of drills to list individually (default: 30). Groups always include every drill."
+                    },
+                    "surface": surface_param()
+                }
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let limit = args.limit.unwrap_or(30);
+        let lua = lua::mining_drills(limit, args.surface.as_deref());
+        let json = execute_lua_json(&self.rcon, &lua).await?;
+        let mut result: MiningDrills = serde_json::from_str(&json)?;
+        for group in &mut result.groups {
+            group.minutes_to_depletion = group.estimate_depletion();
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(json: &str) -> DrillGroup {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_parse_drills() {
+        let json = r#"{"drills":[
+            {"name":"electric-mining-drill","x":10.5,"y":-4.5,"resource":"iron-ore","amount":12000,"status":"working","mining_speed":0.5},
+            {"name":"electric-mining-drill","x":13.5,"y":-4.5,"status":"no_minable_resources","mining_speed":0.5}
+        ],"groups":[
+            {"resource":"iron-ore","drills":1,"statuses":{"working":1},"remaining_amount":12000,"infinite":false,
+             "extraction_per_second":0.5,"output_per_second":0.6}
+        ]}"#;
+        let result: MiningDrills = serde_json::from_str(json).unwrap();
+        assert_eq!(result.drills[0].resource.as_deref(), Some("iron-ore"));
+        assert_eq!(result.drills[0].amount, Some(12000.0));
+        assert_eq!(result.drills[1].resource, None);
+        assert_eq!(result.groups[0].minutes_to_depletion, None);
+    }
+
+    #[test]
+    fn test_depletion_estimate() {
+        // 10 electric drills at 0.5/s each drain 5 ore/s; 30,000 ore lasts 100 minutes
+        let g = group(
+            r#"{"resource":"copper-ore","drills":10,"statuses":{"working":10},"remaining_amount":30000,
+                "infinite":false,"extraction_per_second":5.0,"output_per_second":5.5}"#,
+        );
+        assert_eq!(g.estimate_depletion(), Some(100.0));
+    }
+
+    #[test]
+    fn test_infinite_resource_never_depletes() {
+        let g = group(
+            r#"{"resource":"crude-oil","drills":4,"statuses":{"working":4},"remaining_amount":1200000,
+                "infinite":true,"extraction_per_second":40,"output_per_second":40}"#,
+        );
+        assert_eq!(g.estimate_depletion(), None);
+    }
+
+    #[test]
+    fn test_depleted_group_has_no_estimate() {
+        let g = group(
+            r#"{"drills":3,"statuses":{"no_minable_resources":3},"remaining_amount":0,
+                "infinite":false,"extraction_per_second":0,"output_per_second":0}"#,
+        );
+        assert_eq!(g.estimate_depletion(), None);
+    }
+
+    #[test]
+    fn test_parse_no_drills() {
+        let result: MiningDrills = serde_json::from_str("{}").unwrap();
+        assert!(result.drills.is_empty());
+        assert!(result.groups.is_empty());
+    }
+}
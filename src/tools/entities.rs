@@ -1,20 +1,34 @@
 //! Tool for scanning buildings and structures around the player.
 //!
-//! Uses `find_entities_filtered{position, radius}` and excludes noise entities
-//! (resources, trees, simple-entities) to focus on player-built structures.
-//! Capped at 50 results to keep RCON responses small.
+//! Uses `find_entities_filtered` with a radius or an explicit area, optionally
+//! narrowed to entity names and types. Without a name or type filter, noise
+//! entities (resources, trees, simple-entities) are excluded to focus on
+//! player-built structures. Results are paged (50 per page by default) with a
+//! cursor so RCON responses stay small without silently dropping entities.
+//!
+//! Listing sorts every match before slicing a page, so without a name or type
+//! filter the search is capped at [`MAX_UNFILTERED_RADIUS`] tiles (an area is
+//! clipped to the same extent around its center). Census mode tallies entities
+//! per prototype name in a single pass and is never truncated.
+
+use std::collections::BTreeMap;
 
 use rig::{completion::ToolDefinition, tool::Tool};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
+use super::surface_param;
 use crate::{
     error::SenseiError,
     lua,
     rcon_ext::{execute_lua_json, SharedRcon},
 };
 
-/// Finds up to 50 non-resource, non-decorative entities near the player.
+/// Largest radius (and half-extent of an area) searched when listing without a
+/// name or type filter.
+const MAX_UNFILTERED_RADIUS: f64 = 200.0;
+
+/// Lists or counts entities by area, name, and type.
 pub struct GetNearbyEntities {
     pub(crate) rcon: SharedRcon,
 }
@@ -25,11 +39,52 @@ impl GetNearbyEntities {
     }
 }
 
+/// A bounding box in world coordinates.
+#[derive(Debug, Deserialize)]
+pub struct Area {
+    pub left: f64,
+    pub top: f64,
+    pub right: f64,
+    pub bottom: f64,
+}
+
+impl Area {
+    /// Clip to at most `half` tiles on each side of the center.
+    fn clipped(&self, half: f64) -> (f64, f64, f64, f64) {
+        let (cx, cy) = (
+            (self.left + self.right) / 2.0,
+            (self.top + self.bottom) / 2.0,
+        );
+        (
+            self.left.max(cx - half),
+            self.top.max(cy - half),
+            self.right.min(cx + half),
+            self.bottom.min(cy + half),
+        )
+    }
+}
+
 /// Arguments for [`GetNearbyEntities`]. All optional.
 #[derive(Debug, Deserialize)]
 pub struct GetNearbyEntitiesArgs {
-    /// Search radius in tiles around the player. Defaults to 20.
+    /// Search radius in tiles around the player (or the map origin on another surface).
+    /// Defaults to 20 when listing, and to the whole surface in census mode. Capped at
+    /// [`MAX_UNFILTERED_RADIUS`] when listing without names or types.
     pub radius: Option<f64>,
+    /// Bounding box to search instead of a radius.
+    pub area: Option<Area>,
+    /// Only these entity prototype names (e.g. "gun-turret").
+    pub names: Option<Vec<String>>,
+    /// Only these entity types (e.g. "assembling-machine", "tree").
+    pub types: Option<Vec<String>>,
+    /// `next_cursor` from the previous page. Ignored in census mode.
+    pub cursor: Option<u32>,
+    /// Max entities per page. Defaults to 50, max 200. Ignored in census mode.
+    pub limit: Option<u32>,
+    /// Count entities per prototype name instead of listing them.
+    pub census: Option<bool>,
+    #[doc = surface_arg_doc!()]
+    pub surface: Option<String>,
 }
 
 /// A single entity found near the player.
@@ -46,10 +101,20 @@ pub struct NearbyEntity {
     pub y: f64,
 }
 
-/// Top-level response wrapper. Capped at 50 entities.
+/// Top-level response: one page of entities, or counts by name in census mode.
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct NearbyEntities {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub entities: Vec<NearbyEntity>,
+    /// Entity prototype name → count (census mode).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub counts: BTreeMap<String, u32>,
+    /// Entities matching the filters, across all pages.
+    #[serde(default)]
+    pub total: u32,
+    /// Pass as `cursor` to fetch the next page; absent on the last page.
+    #[serde(default)]
+    pub next_cursor: Option<u32>,
 }
 
 impl Tool for GetNearbyEntities {
@@ -61,22 +126,80 @@ impl Tool for GetNearbyEntities {
     async fn definition(&self, _prompt: String) -> ToolDefinition {
         ToolDefinition {
             name: "get_nearby_entities".to_string(),
-            description: "Get buildings and structures near the player (excludes resources, trees, and decoratives). Returns up to 50 entities.".to_string(),
+            description: "Get entities near the player or in an area, optionally filtered by entity names and types. Without filters, resources, trees, and rocks are excluded. Lists one page at a time (total and next_cursor tell if there is more), or with census=true counts entities per name — use census for whole-surface counts.".to_string(),
             parameters: json!({
                 "type": "object",
                 "properties": {
                     "radius": {
                         "type": "number",
-                        "description": "Search radius in tiles (default: 20)"
-                    }
+                        "description": "Search radius in tiles (default: 20 when listing, the whole surface in census mode; listing without names or types is capped at 200)"
+                    },
+                    "area": {
+                        "type": "object",
+                        "description": "Bounding box to search instead of a radius",
+                        "properties": {
+                            "left": { "type": "number" },
+                            "top": { "type": "number" },
+                            "right": { "type": "number" },
+                            "bottom": { "type": "number" }
+                        },
+                        "required": ["left", "top", "right", "bottom"]
+                    },
+                    "names": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Only these entity names (e.g. ['gun-turret', 'laser-turret'])"
+                    },
+                    "types": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Only these entity types (e.g. ['assembling-machine', 'furnace'])"
+                    },
+                    "cursor": {
+                        "type": "integer",
+                        "description": "next_cursor from the previous page to continue listing"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum entities per page (default: 50, max: 200)"
+                    },
+                    "census": {
+                        "type": "boolean",
+                        "description": "Count matching entities per name instead of listing them (default: false)"
+                    },
+                    "surface": surface_param()
                 }
             }),
         }
     }
 
     async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
-        let radius = args.radius.unwrap_or(20.0);
-        let lua = lua::nearby_entities(radius);
+        let census = args.census.unwrap_or(false);
+        let names = args.names.unwrap_or_default();
+        let types = args.types.unwrap_or_default();
+        // Unfiltered listing sorts every match, so keep it to a bounded extent
+        let cap = if !census && names.is_empty() && types.is_empty() {
+            MAX_UNFILTERED_RADIUS
+        } else {
+            f64::INFINITY
+        };
+        let filter = lua::EntityFilter {
+            names: &names,
+            types: &types,
+            area: args.area.map(|a| a.clipped(cap)),
+            radius: if census {
+                args.radius
+            } else {
+                Some(args.radius.unwrap_or(20.0).min(cap))
+            },
+        };
+        let surface = args.surface.as_deref();
+        let lua = if census {
+            lua::entity_census(&filter, surface)
+        } else {
+            let limit = args.limit.unwrap_or(50).min(200);
+            lua::nearby_entities(&filter, args.cursor.unwrap_or(0), limit, surface)
+        };
         let json = execute_lua_json(&self.rcon, &lua).await?;
         Ok(serde_json::from_str(&json)?)
     }
@@ -96,6 +219,26 @@ mod tests {
         assert_eq!(result.entities.len(), 2);
         assert_eq!(result.entities[0].name, "stone-furnace");
         assert_eq!(result.entities[0].kind, "furnace");
+        assert_eq!(result.next_cursor, None);
+    }
+
+    #[test]
+    fn test_parse_page_with_cursor() {
+        let json = r#"{"entities":[{"name":"inserter","type":"inserter","x":0.5,"y":0.5}],
+            "total":120,"next_cursor":50}"#;
+        let result: NearbyEntities = serde_json::from_str(json).unwrap();
+        assert_eq!(result.total, 120);
+        assert_eq!(result.next_cursor, Some(50));
+    }
+
+    #[test]
+    fn test_parse_census() {
+        let json = r#"{"total":5230,"counts":{"transport-belt":4800,"inserter":400,"assembling-machine-2":30}}"#;
+        let result: NearbyEntities = serde_json::from_str(json).unwrap();
+        assert!(result.entities.is_empty());
+        assert_eq!(result.counts["transport-belt"], 4800);
+        assert_eq!(result.total, 5230);
+        assert_eq!(result.next_cursor, None);
     }
 
     #[test]
@@ -104,4 +247,16 @@ mod tests {
         let result: NearbyEntities = serde_json::from_str(json).unwrap();
         assert!(result.entities.is_empty());
     }
+
+    #[test]
+    fn test_area_clipped_around_center() {
+        let area = Area {
+            left: -1000.0,
+            top: 0.0,
+            right: 1000.0,
+            bottom: 100.0,
+        };
+        assert_eq!(area.clipped(200.0), (-200.0, 0.0, 200.0, 100.0));
+        assert_eq!(area.clipped(f64::INFINITY), (-1000.0, 0.0, 1000.0, 100.0));
+    }
 }
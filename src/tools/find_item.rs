@@ -0,0 +1,139 @@
+//! Tool for answering "where did I put my copper cable?".
+//!
+//! Searches every chest (including logistic and linked chests), cargo wagon, and
+//! assembler/furnace output inventory of the player's force on a surface — not
+//! limited to a radius like `get_nearby_entities` — and reports the total, the
+//! nearest containers holding the item, and the count per container type.
+
+use std::collections::BTreeMap;
+
+use rig::{completion::ToolDefinition, tool::Tool};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::surface_param;
+use crate::{
+    error::SenseiError,
+    lua,
+    rcon_ext::{execute_lua_json, SharedRcon},
+};
+
+/// Finds containers holding an item anywhere on a surface.
+pub struct FindItem {
+    pub(crate) rcon: SharedRcon,
+}
+
+impl FindItem {
+    pub const fn new(rcon: SharedRcon) -> Self {
+        Self { rcon }
+    }
+}
+
+/// Arguments for [`FindItem`].
+#[derive(Debug, Deserialize)]
+pub struct FindItemArgs {
+    /// Item prototype name (e.g. "copper-cable").
+    pub item: String,
+    /// Max locations to return, nearest first. Defaults to 20, max 100.
+    pub limit: Option<u32>,
+    #[doc = surface_arg_doc!()]
+    pub surface: Option<String>,
+}
+
+/// A container holding the item.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ItemLocation {
+    /// Entity prototype name (e.g. "steel-chest", "cargo-wagon").
+    pub name: String,
+    /// Entity type (e.g. "container", "logistic-container", "assembling-machine").
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub x: f64,
+    pub y: f64,
+    /// Items of this kind in the container.
+    pub count: u64,
+    /// Distance from the player in tiles.
+    pub distance: f64,
+}
+
+/// Top-level response: where an item is stored on the surface.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ItemLocations {
+    pub item: String,
+    /// Items across every container found.
+    pub total: u64,
+    /// Containers holding the item.
+    pub containers: u32,
+    /// Nearest containers first.
+    #[serde(default)]
+    pub locations: Vec<ItemLocation>,
+    /// Item count per container prototype, across every container found.
+    #[serde(default)]
+    pub by_container: BTreeMap<String, u64>,
+}
+
+impl Tool for FindItem {
+    const NAME: &'static str = "find_item";
+    type Error = SenseiError;
+    type Args = FindItemArgs;
+    type Output = ItemLocations;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "find_item".to_string(),
+            description: "Find where an item is stored: searches chests, logistic chests, cargo wagons, and assembler/furnace outputs across the whole surface. Returns the total count, the nearest containers holding it with their counts, and the count per container type.".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "item": {
+                        "type": "string",
+                        "description": "Item name (e.g. 'copper-cable', 'iron-gear-wheel')"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of locations to return, nearest first (default: 20, max: 100)"
+                    },
+                    "surface": surface_param()
+                },
+                "required": ["item"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let limit = args.limit.unwrap_or(20).min(100);
+        let lua = lua::find_item(&args.item, limit, args.surface.as_deref());
+        let json = execute_lua_json(&self.rcon, &lua).await?;
+        let result: ItemLocations = serde_json::from_str(&json)?;
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_item_locations() {
+        let json = r#"{"item":"copper-cable","total":1450,"containers":3,
+            "locations":[
+              {"name":"wooden-chest","type":"container","x":3.5,"y":-2.5,"count":50,"distance":4.3},
+              {"name":"assembling-machine-2","type":"assembling-machine","x":20.5,"y":8.5,"count":200,"distance":22.2},
+              {"name":"passive-provider-chest","type":"logistic-container","x":-80.5,"y":40.5,"count":1200,"distance":90.1}
+            ],
+            "by_container":{"wooden-chest":50,"assembling-machine-2":200,"passive-provider-chest":1200}}"#;
+        let result: ItemLocations = serde_json::from_str(json).unwrap();
+        assert_eq!(result.total, 1450);
+        assert_eq!(result.locations[0].kind, "container");
+        assert_eq!(result.locations[2].count, 1200);
+        assert_eq!(result.by_container["passive-provider-chest"], 1200);
+    }
+
+    #[test]
+    fn test_parse_item_not_found() {
+        let json = r#"{"item":"rocket-fuel","total":0,"containers":0}"#;
+        let result: ItemLocations = serde_json::from_str(json).unwrap();
+        assert!(result.locations.is_empty());
+        assert!(result.by_container.is_empty());
+    }
+}
@@ -0,0 +1,206 @@
+//! Tool for fluid production statistics (water, steam, oil products, acids).
+//!
+//! The fluid counterpart of the item production tools: reads
+//! `force.get_fluid_production_statistics(surface)` over one flow precision
+//! window and reports both a single fluid's totals/rates and the factory-wide
+//! fluid rankings (top produced, top consumed, largest deficits). Lets Sensei
+//! diagnose oil processing and nuclear steam balance.
+
+use rig::{completion::ToolDefinition, tool::Tool};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::{
+    overview::{FlowRate, FlowRow, FlowRows, ProductionOverview},
+    production::FlowWindow,
+    surface_param,
+};
+use crate::{
+    error::SenseiError,
+    lua,
+    rcon_ext::{execute_lua_json, SharedRcon},
+};
+
+/// Reads fluid production statistics, optionally focusing on one fluid.
+pub struct GetFluidProduction {
+    pub(crate) rcon: SharedRcon,
+}
+
+impl GetFluidProduction {
+    pub const fn new(rcon: SharedRcon) -> Self {
+        Self { rcon }
+    }
+}
+
+/// Arguments for [`GetFluidProduction`]. All optional.
+#[derive(Debug, Deserialize)]
+pub struct GetFluidProductionArgs {
+    /// Fluid prototype name to report in detail (e.g. "petroleum-gas", "steam").
+    pub fluid: Option<String>,
+    /// Time window for the rates. Defaults to 10 minutes.
+    pub window: Option<FlowWindow>,
+    /// Number of entries in each ranking. Defaults to 10.
+    pub top: Option<usize>,
+    #[doc = surface_arg_doc!()]
+    pub surface: Option<String>,
+}
+
+/// Totals and rates for a single fluid.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct FluidStats {
+    /// Fluid prototype name.
+    pub name: String,
+    /// All-time units produced.
+    pub produced: f64,
+    /// All-time units consumed.
+    pub consumed: f64,
+    /// Average units produced per minute over the window.
+    pub produced_per_min: f64,
+    /// Average units consumed per minute over the window.
+    pub consumed_per_min: f64,
+    /// `produced_per_min - consumed_per_min`; negative means a deficit.
+    pub net_per_min: f64,
+}
+
+impl From<FlowRow> for FluidStats {
+    fn from(row: FlowRow) -> Self {
+        Self {
+            net_per_min: row.produced_per_min - row.consumed_per_min,
+            name: row.name,
+            produced: row.produced.unwrap_or_default(),
+            consumed: row.consumed.unwrap_or_default(),
+            produced_per_min: row.produced_per_min,
+            consumed_per_min: row.consumed_per_min,
+        }
+    }
+}
+
+/// Fluid statistics response: optional single-fluid detail plus rankings.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct FluidProduction {
+    /// Detail for the requested fluid, or `None` if no fluid was requested.
+    /// A fluid that never flowed is reported with all zeros.
+    pub fluid: Option<FluidStats>,
+    /// Top produced/consumed fluids and deficits over the window.
+    #[serde(flatten)]
+    pub overview: ProductionOverview,
+}
+
+impl FluidProduction {
+    /// Build the response from raw rows, pulling out `fluid` if requested.
+    fn from_rows(window: FlowWindow, rows: Vec<FlowRow>, fluid: Option<&str>, top: usize) -> Self {
+        let rates: Vec<FlowRate> = rows.iter().cloned().map(FlowRate::from).collect();
+        let overview = ProductionOverview::rank(window, &rates, top);
+        let fluid = fluid.map(|name| {
+            rows.into_iter().find(|row| row.name == name).map_or_else(
+                || FluidStats {
+                    name: name.to_string(),
+                    produced: 0.0,
+                    consumed: 0.0,
+                    produced_per_min: 0.0,
+                    consumed_per_min: 0.0,
+                    net_per_min: 0.0,
+                },
+                FluidStats::from,
+            )
+        });
+        Self { fluid, overview }
+    }
+}
+
+impl Tool for GetFluidProduction {
+    const NAME: &'static str = "get_fluid_production";
+    type Error = SenseiError;
+    type Args = GetFluidProductionArgs;
+    type Output = FluidProduction;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "get_fluid_production".to_string(),
+            description: "Get fluid production statistics (water, steam, crude oil, petroleum gas, light/heavy oil, sulfuric acid, lubricant): per-minute rates over a time window, top producers/consumers, and largest deficits. Optionally details one fluid including all-time totals.".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "fluid": {
+                        "type": "string",
+                        "description": "Fluid prototype name to report in detail (e.g. 'petroleum-gas', 'steam')"
+                    },
+                    "window": {
+                        "type": "string",
+                        "enum": FlowWindow::NAMES,
+                        "description": "Time window for the per-minute rates (default: '10m')"
+                    },
+                    "top": {
+                        "type": "integer",
+                        "description": "Number of entries in each ranking (default: 10)"
+                    },
+                    "surface": surface_param()
+                }
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let window = args.window.unwrap_or_default();
+        let lua = lua::fluid_production_overview(
+            window.precision_index(),
+            window.minutes(),
+            args.fluid.as_deref(),
+            args.surface.as_deref(),
+        );
+        let json = execute_lua_json(&self.rcon, &lua).await?;
+        let rows: FlowRows = serde_json::from_str(&json)?;
+        Ok(FluidProduction::from_rows(
+            window,
+            rows.items,
+            args.fluid.as_deref(),
+            args.top.unwrap_or(10),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROWS: &str = r#"{"items":[
+        {"name":"water","produced_per_min":12000,"consumed_per_min":11500},
+        {"name":"heavy-oil","produced_per_min":250,"consumed_per_min":80},
+        {"name":"petroleum-gas","produced":90000,"consumed":95000,"produced_per_min":550,"consumed_per_min":700}
+    ]}"#;
+
+    #[test]
+    fn test_fluid_detail_and_deficits() {
+        let rows: FlowRows = serde_json::from_str(ROWS).unwrap();
+        let result = FluidProduction::from_rows(
+            FlowWindow::TenMinutes,
+            rows.items,
+            Some("petroleum-gas"),
+            10,
+        );
+        let gas = result.fluid.unwrap();
+        assert_eq!(gas.produced, 90000.0);
+        assert_eq!(gas.net_per_min, -150.0);
+        assert_eq!(result.overview.deficits.len(), 1);
+        assert_eq!(result.overview.deficits[0].name, "petroleum-gas");
+        assert_eq!(result.overview.top_produced[0].name, "water");
+    }
+
+    #[test]
+    fn test_unknown_fluid_reports_zero() {
+        let rows: FlowRows = serde_json::from_str(ROWS).unwrap();
+        let result =
+            FluidProduction::from_rows(FlowWindow::OneHour, rows.items, Some("lubricant"), 10);
+        let lube = result.fluid.unwrap();
+        assert_eq!(lube.name, "lubricant");
+        assert_eq!(lube.produced_per_min, 0.0);
+    }
+
+    #[test]
+    fn test_overview_only() {
+        let rows: FlowRows = serde_json::from_str("{}").unwrap();
+        let result = FluidProduction::from_rows(FlowWindow::OneMinute, rows.items, None, 10);
+        assert_eq!(result.fluid, None);
+        assert!(result.overview.top_consumed.is_empty());
+    }
+}
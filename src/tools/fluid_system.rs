@@ -0,0 +1,382 @@
+//! Tool for tracing a pipe network: pipes, tanks, pumpjacks, and oil processing.
+//!
+//! Starting from the entity at a position, fluidbox connections are walked to
+//! collect every connected entity (capped). Reports fluid segment contents,
+//! storage tank levels, pumpjacks, and refineries/chemical plants with status
+//! and per-fluidbox contents.
+//!
+//! Pumpjack yield and output follow the pumpjack rule in the knowledge base
+//! (`data/wiki/factorio-mechanics.md`) — 10 units/s at 100% yield
+//! (`amount / normal_resource_amount`), scaled by mining speed and bonuses.
+//! Issues such as a machine stopped by a full output and the full tank behind it
+//! are derived on the Rust side.
+
+use std::collections::BTreeMap;
+
+use rig::{completion::ToolDefinition, tool::Tool};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::surface_param;
+use crate::{
+    error::SenseiError,
+    lua,
+    rcon_ext::{execute_lua_json, SharedRcon},
+};
+
+/// Fluid per second from a pumpjack at 100% yield and mining speed 1.
+const UNITS_PER_SECOND_AT_FULL_YIELD: f64 = 10.0;
+/// Fill ratio at which a tank or fluidbox counts as full.
+const FULL_RATIO: f64 = 0.95;
+
+/// Inspects the fluid network connected to an entity.
+pub struct GetFluidSystem {
+    pub(crate) rcon: SharedRcon,
+}
+
+impl GetFluidSystem {
+    pub const fn new(rcon: SharedRcon) -> Self {
+        Self { rcon }
+    }
+}
+
+/// Arguments for [`GetFluidSystem`].
+#[derive(Debug, Deserialize)]
+pub struct GetFluidSystemArgs {
+    /// X coordinate of any entity in the fluid system (pipe, tank, machine, pumpjack).
+    pub x: f64,
+    /// Y coordinate of that entity.
+    pub y: f64,
+    /// Max connected entities to walk. Defaults to 500, max 2000.
+    pub limit: Option<u32>,
+    #[doc = surface_arg_doc!()]
+    pub surface: Option<String>,
+}
+
+/// One fluidbox of an entity.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct FluidBoxInfo {
+    /// Fluid held, or the fluid the box is filtered to when empty.
+    pub fluid: Option<String>,
+    pub amount: f64,
+    pub capacity: f64,
+    pub temperature: Option<f64>,
+    /// "input", "output", "input-output", or "none".
+    pub production_type: Option<String>,
+}
+
+impl FluidBoxInfo {
+    fn fill_ratio(&self) -> f64 {
+        if self.capacity > 0.0 {
+            self.amount / self.capacity
+        } else {
+            0.0
+        }
+    }
+}
+
+/// The entity the walk started from.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct StartEntity {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub x: f64,
+    pub y: f64,
+    #[serde(default)]
+    pub fluidboxes: Vec<FluidBoxInfo>,
+}
+
+/// A connected pipe segment (pipes and pipe-like boxes sharing one fluid).
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct FluidSegment {
+    pub id: u64,
+    /// Fluid name → amount in the segment. Empty for a dry segment.
+    #[serde(default)]
+    pub fluids: BTreeMap<String, f64>,
+}
+
+/// A storage tank in the system.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct TankInfo {
+    pub name: String,
+    pub x: f64,
+    pub y: f64,
+    pub fluid: Option<String>,
+    pub amount: f64,
+    pub capacity: f64,
+    /// `amount / capacity`, 0.0–1.0.
+    #[serde(default)]
+    pub fill_ratio: f64,
+}
+
+/// A pumpjack (or other fluid-mining drill) in the system.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct PumpjackInfo {
+    pub name: String,
+    pub x: f64,
+    pub y: f64,
+    pub status: Option<String>,
+    /// Resource being extracted (e.g. "crude-oil").
+    pub resource: Option<String>,
+    /// Current resource amount of the well.
+    pub amount: f64,
+    /// Amount that equals 100% yield.
+    pub normal_amount: Option<f64>,
+    pub mining_speed: f64,
+    #[serde(default)]
+    pub speed_bonus: f64,
+    #[serde(default)]
+    pub productivity_bonus: f64,
+    /// Well yield in percent.
+    #[serde(default)]
+    pub yield_percent: Option<f64>,
+    /// Fluid output per second at that yield with speed and productivity bonuses.
+    #[serde(default)]
+    pub per_second: Option<f64>,
+}
+
+impl PumpjackInfo {
+    fn rate(&mut self) {
+        let Some(normal) = self.normal_amount.filter(|n| *n > 0.0) else {
+            return;
+        };
+        let yield_percent = self.amount / normal * 100.0;
+        self.yield_percent = Some(yield_percent);
+        self.per_second = Some(
+            UNITS_PER_SECOND_AT_FULL_YIELD * yield_percent / 100.0
+                * self.mining_speed
+                * (1.0 + self.speed_bonus)
+                * (1.0 + self.productivity_bonus),
+        );
+    }
+}
+
+/// A crafting machine (refinery, chemical plant, ...) in the system.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct FluidMachine {
+    pub name: String,
+    pub x: f64,
+    pub y: f64,
+    pub recipe: Option<String>,
+    /// `defines.entity_status` name (e.g. "working", "full_output", "no_input_fluid").
+    pub status: Option<String>,
+    #[serde(default)]
+    pub fluidboxes: Vec<FluidBoxInfo>,
+}
+
+/// Top-level response: the fluid system around the starting entity.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct FluidSystem {
+    pub start: StartEntity,
+    /// Connected entities visited.
+    pub entities: u32,
+    /// Whether the walk stopped at the entity limit.
+    pub truncated: bool,
+    pub pipes: u32,
+    #[serde(default)]
+    pub segments: Vec<FluidSegment>,
+    #[serde(default)]
+    pub tanks: Vec<TankInfo>,
+    #[serde(default)]
+    pub pumpjacks: Vec<PumpjackInfo>,
+    #[serde(default)]
+    pub machines: Vec<FluidMachine>,
+    /// Other connected entities (pumps, boilers, offshore pumps, ...) by name.
+    #[serde(default)]
+    pub other: BTreeMap<String, u32>,
+    /// Combined output of the pumpjacks, per second.
+    #[serde(default)]
+    pub pumpjack_output_per_second: f64,
+    /// Plain-language problems found in the system.
+    #[serde(default)]
+    pub issues: Vec<String>,
+}
+
+impl FluidSystem {
+    /// Fill tank ratios and pumpjack rates, then explain stopped machines.
+    fn diagnose(&mut self) {
+        for tank in &mut self.tanks {
+            tank.fill_ratio = if tank.capacity > 0.0 {
+                tank.amount / tank.capacity
+            } else {
+                0.0
+            };
+        }
+        for pumpjack in &mut self.pumpjacks {
+            pumpjack.rate();
+        }
+        self.pumpjack_output_per_second = self.pumpjacks.iter().filter_map(|p| p.per_second).sum();
+
+        let mut issues = Vec::new();
+        for m in &self.machines {
+            let at = format!("{} at ({:.1}, {:.1})", m.name, m.x, m.y);
+            match m.status.as_deref() {
+                Some("full_output") => {
+                    for fluid in m
+                        .fluidboxes
+                        .iter()
+                        .filter(|b| b.production_type.as_deref() == Some("output"))
+                        .filter(|b| b.fill_ratio() >= FULL_RATIO)
+                        .filter_map(|b| b.fluid.as_deref())
+                    {
+                        let full_tanks = self
+                            .tanks
+                            .iter()
+                            .filter(|t| t.fluid.as_deref() == Some(fluid))
+                            .filter(|t| t.fill_ratio >= FULL_RATIO)
+                            .count();
+                        if full_tanks > 0 {
+                            issues.push(format!(
+                                "{at} is stopped: {fluid} output is full and {full_tanks} \
+                                 {fluid} tank(s) are full — nothing is consuming {fluid}"
+                            ));
+                        } else {
+                            issues.push(format!("{at} is stopped: {fluid} output is full"));
+                        }
+                    }
+                }
+                Some("no_input_fluid" | "no_ingredients") => {
+                    for fluid in m
+                        .fluidboxes
+                        .iter()
+                        .filter(|b| b.production_type.as_deref() == Some("input"))
+                        .filter(|b| b.amount <= 0.0)
+                        .filter_map(|b| b.fluid.as_deref())
+                    {
+                        issues.push(format!("{at} has no {fluid} input"));
+                    }
+                }
+                _ => {}
+            }
+        }
+        let blocked = self
+            .pumpjacks
+            .iter()
+            .filter(|p| p.status.as_deref() == Some("waiting_for_space_in_destination"))
+            .count();
+        if blocked > 0 {
+            issues.push(format!(
+                "{blocked} pumpjack(s) are waiting for space — the line they feed is backed up"
+            ));
+        }
+        if self.truncated {
+            issues.push("the walk stopped at the entity limit; the system is larger".to_string());
+        }
+        self.issues = issues;
+    }
+}
+
+impl Tool for GetFluidSystem {
+    const NAME: &'static str = "get_fluid_system";
+    type Error = SenseiError;
+    type Args = GetFluidSystemArgs;
+    type Output = FluidSystem;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "get_fluid_system".to_string(),
+            description: "Inspect the fluid network connected to the entity at a position (pipe, tank, pumpjack, refinery, chemical plant). Reports pipe segment contents, storage tank levels, pumpjack yields and output per second, refinery and chemical plant status with each fluidbox's contents, and issues such as a machine stopped by a full output tank.".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "x": {
+                        "type": "number",
+                        "description": "X coordinate of any entity in the fluid system"
+                    },
+                    "y": {
+                        "type": "number",
+                        "description": "Y coordinate of that entity"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum connected entities to walk (default: 500, max: 2000)"
+                    },
+                    "surface": surface_param()
+                },
+                "required": ["x", "y"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let limit = args.limit.unwrap_or(500).min(2000);
+        let lua = lua::fluid_system(args.x, args.y, limit, args.surface.as_deref());
+        let json = execute_lua_json(&self.rcon, &lua).await?;
+        let mut result: FluidSystem = serde_json::from_str(&json)?;
+        result.diagnose();
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn system(json: &str) -> FluidSystem {
+        let mut s: FluidSystem = serde_json::from_str(json).unwrap();
+        s.diagnose();
+        s
+    }
+
+    #[test]
+    fn test_full_heavy_oil_tank_stops_refinery() {
+        let s = system(
+            r#"{"start":{"name":"chemical-plant","type":"assembling-machine","x":20.5,"y":0.5},
+                "entities":40,"truncated":false,"pipes":30,
+                "segments":[{"id":7,"fluids":{"heavy-oil":2500}},{"id":8}],
+                "tanks":[{"name":"storage-tank","x":10,"y":10,"fluid":"heavy-oil","amount":25000,"capacity":25000}],
+                "machines":[
+                  {"name":"oil-refinery","x":0.5,"y":0.5,"recipe":"advanced-oil-processing","status":"full_output",
+                   "fluidboxes":[
+                     {"fluid":"crude-oil","amount":100,"capacity":100,"production_type":"input"},
+                     {"fluid":"heavy-oil","amount":100,"capacity":100,"production_type":"output"},
+                     {"fluid":"petroleum-gas","amount":10,"capacity":100,"production_type":"output"}]},
+                  {"name":"chemical-plant","x":20.5,"y":0.5,"recipe":"plastic-bar","status":"no_input_fluid",
+                   "fluidboxes":[{"fluid":"petroleum-gas","amount":0,"capacity":100,"production_type":"input"}]}
+                ]}"#,
+        );
+        assert_eq!(s.tanks[0].fill_ratio, 1.0);
+        assert!(s.segments[1].fluids.is_empty());
+        assert_eq!(s.issues.len(), 2);
+        assert!(s.issues[0].starts_with("oil-refinery at (0.5, 0.5) is stopped: heavy-oil"));
+        assert!(s.issues[0].contains("1 heavy-oil tank(s) are full"));
+        assert_eq!(
+            s.issues[1],
+            "chemical-plant at (20.5, 0.5) has no petroleum-gas input"
+        );
+    }
+
+    #[test]
+    fn test_pumpjack_yield_and_rate() {
+        // 150000 of 300000 = 50% yield → 5/s; speed bonus +50% → 7.5/s
+        let s = system(
+            r#"{"start":{"name":"pumpjack","type":"mining-drill","x":0.5,"y":0.5},
+                "entities":3,"truncated":false,"pipes":2,
+                "pumpjacks":[
+                  {"name":"pumpjack","x":0.5,"y":0.5,"status":"working","resource":"crude-oil",
+                   "amount":150000,"normal_amount":300000,"mining_speed":1,"speed_bonus":0.5,"productivity_bonus":0},
+                  {"name":"pumpjack","x":5.5,"y":0.5,"status":"waiting_for_space_in_destination","resource":"crude-oil",
+                   "amount":300000,"normal_amount":300000,"mining_speed":1}
+                ]}"#,
+        );
+        assert_eq!(s.pumpjacks[0].yield_percent, Some(50.0));
+        assert!((s.pumpjacks[0].per_second.unwrap() - 7.5).abs() < 1e-9);
+        assert!((s.pumpjack_output_per_second - 17.5).abs() < 1e-9);
+        assert_eq!(s.issues.len(), 1);
+        assert!(s.issues[0].contains("1 pumpjack(s) are waiting for space"));
+    }
+
+    #[test]
+    fn test_truncated_walk_is_reported() {
+        let s = system(
+            r#"{"start":{"name":"pipe","type":"pipe","x":0.5,"y":0.5},
+                "entities":500,"truncated":true,"pipes":500,"other":{"pump":2}}"#,
+        );
+        assert_eq!(s.other["pump"], 2);
+        assert_eq!(
+            s.issues,
+            vec!["the walk stopped at the entity limit; the system is larger"]
+        );
+    }
+}
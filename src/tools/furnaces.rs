@@ -1,21 +1,29 @@
-//! Tool for listing furnaces with their smelting recipes, fuel, and output.
+//! Tool for inspecting furnaces: recipes, fuel, input ore, output backlog, and status.
 //!
 //! Finds entities of `type="furnace"` up to a configurable limit. For each
-//! furnace, inspects the recipe, fuel inventory (first fuel item), and output
-//! inventory (first output item). All three are optional — an idle furnace
-//! with no fuel will have all `None`.
+//! furnace, reports the recipe, full fuel/input/output inventory counts, the
+//! remaining burn time (burning fuel plus fuel inventory at full power), and
+//! the entity `status` (no_fuel, no_ingredients, full_output, ...). Electric
+//! furnaces have no fuel and `fuel_seconds` is `None`.
+//!
+//! In summary mode, every furnace on the surface is grouped by furnace type
+//! and recipe — the way to answer "are my furnaces starving?" on a large
+//! smelting array without listing hundreds of machines.
+
+use std::collections::BTreeMap;
 
 use rig::{completion::ToolDefinition, tool::Tool};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
+use super::{inventory::InventoryItem, surface_param};
 use crate::{
     error::SenseiError,
     lua,
     rcon_ext::{execute_lua_json, SharedRcon},
 };
 
-/// Lists furnaces with their positions, active recipes, fuel types, and output items.
+/// Lists furnaces with their positions, recipes, inventories, burn time, and status.
 pub struct GetFurnaces {
     pub(crate) rcon: SharedRcon,
 }
@@ -29,8 +37,12 @@ impl GetFurnaces {
 /// Arguments for [`GetFurnaces`]. All optional.
 #[derive(Debug, Deserialize)]
 pub struct GetFurnacesArgs {
-    /// Max furnaces to return. Defaults to 30 if omitted.
+    /// Max furnaces to return. Defaults to 30 if omitted. Ignored in summary mode.
     pub limit: Option<u32>,
+    /// Group every furnace by type and recipe instead of listing furnaces.
+    pub summary: Option<bool>,
+    #[doc = surface_arg_doc!()]
+    pub surface: Option<String>,
 }
 
 /// A single furnace's state snapshot.
@@ -44,16 +56,49 @@ pub struct FurnaceInfo {
     pub y: f64,
     /// Active smelting recipe, or `None` if the furnace is idle.
     pub recipe: Option<String>,
-    /// First item in the fuel inventory (e.g. "coal"), or `None` if empty/electric.
-    pub fuel_type: Option<String>,
-    /// First item in the output inventory, or `None` if empty.
-    pub output_item: Option<String>,
+    /// `defines.entity_status` name (e.g. "working", "no_fuel", "full_output").
+    pub status: Option<String>,
+    /// Fuel inventory contents (empty for electric furnaces).
+    #[serde(default)]
+    pub fuel: Vec<InventoryItem>,
+    /// Seconds the furnace can run at full power on its remaining fuel, or `None` if electric.
+    pub fuel_seconds: Option<f64>,
+    /// Source inventory contents (ore/plates waiting to be smelted).
+    #[serde(default)]
+    pub input: Vec<InventoryItem>,
+    /// Result inventory contents (output waiting to be picked up).
+    #[serde(default)]
+    pub output: Vec<InventoryItem>,
+}
+
+/// Furnaces sharing one furnace type and recipe.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct FurnaceGroup {
+    /// Furnace prototype name.
+    pub name: String,
+    /// The shared recipe, or `None` for furnaces that have not started smelting.
+    pub recipe: Option<String>,
+    /// Number of furnaces in this group.
+    pub machines: u32,
+    /// Furnace count per `defines.entity_status` name.
+    pub statuses: BTreeMap<String, u32>,
+    /// Total items waiting in source inventories.
+    pub input_items: u64,
+    /// Total items waiting in result inventories (output backlog).
+    pub output_items: u64,
+    /// Total items in fuel inventories.
+    pub fuel_items: u64,
+    /// Shortest remaining burn time in the group, or `None` for electric furnaces.
+    pub min_fuel_seconds: Option<f64>,
 }
 
-/// Top-level response wrapper for the furnaces list.
+/// Top-level response: a furnace list, or furnace groups in summary mode.
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct Furnaces {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub furnaces: Vec<FurnaceInfo>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<FurnaceGroup>,
 }
 
 impl Tool for GetFurnaces {
@@ -65,23 +110,31 @@ impl Tool for GetFurnaces {
     async fn definition(&self, _prompt: String) -> ToolDefinition {
         ToolDefinition {
             name: "get_furnaces".to_string(),
-            description: "Get furnaces on the map with their recipes, fuel types, and output items"
-                .to_string(),
+            description: "Get furnaces on the map with their recipes, fuel counts and remaining burn time, input ore counts, output backlog, and status (working, no_fuel, no_ingredients, full_output, ...). Use summary mode to group all furnaces by type and recipe — best for checking whether a large smelting array is starving.".to_string(),
             parameters: json!({
                 "type": "object",
                 "properties": {
                     "limit": {
                         "type": "integer",
-                        "description": "Maximum number of furnaces to return (default: 30)"
-                    }
+                        "description": "Maximum number of furnaces to return (default: 30, ignored in summary mode)"
+                    },
+                    "summary": {
+                        "type": "boolean",
+                        "description": "Group every furnace by type and recipe with status counts and inventory totals instead of listing furnaces (default: false)"
+                    },
+                    "surface": surface_param()
                 }
             }),
         }
     }
 
     async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
-        let limit = args.limit.unwrap_or(30);
-        let lua = lua::furnaces(limit);
+        let surface = args.surface.as_deref();
+        let lua = if args.summary.unwrap_or(false) {
+            lua::furnace_summary(surface)
+        } else {
+            lua::furnaces(args.limit.unwrap_or(30), surface)
+        };
         let json = execute_lua_json(&self.rcon, &lua).await?;
         Ok(serde_json::from_str(&json)?)
     }
@@ -94,21 +147,52 @@ mod tests {
     #[test]
     fn test_parse_furnaces() {
         let json = r#"{"furnaces":[
-            {"name":"stone-furnace","x":1.0,"y":2.0,"recipe":"iron-plate","fuel_type":"coal","output_item":"iron-plate"},
-            {"name":"steel-furnace","x":4.0,"y":2.0,"recipe":null,"fuel_type":null,"output_item":null}
+            {"name":"stone-furnace","x":1.0,"y":2.0,"recipe":"iron-plate","status":"working",
+             "fuel":[{"name":"coal","count":12}],"fuel_seconds":480.5,
+             "input":[{"name":"iron-ore","count":30}],"output":[{"name":"iron-plate","count":7}]},
+            {"name":"steel-furnace","x":4.0,"y":2.0,"recipe":null,"fuel_seconds":0}
         ]}"#;
         let result: Furnaces = serde_json::from_str(json).unwrap();
         assert_eq!(result.furnaces.len(), 2);
         assert_eq!(result.furnaces[0].recipe.as_deref(), Some("iron-plate"));
-        assert_eq!(result.furnaces[0].fuel_type.as_deref(), Some("coal"));
+        assert_eq!(result.furnaces[0].fuel[0].name, "coal");
+        assert_eq!(result.furnaces[0].fuel[0].count, 12);
+        assert_eq!(result.furnaces[0].fuel_seconds, Some(480.5));
+        assert_eq!(result.furnaces[0].input[0].count, 30);
+        assert_eq!(result.furnaces[0].output[0].count, 7);
+        assert_eq!(result.furnaces[0].status.as_deref(), Some("working"));
         assert_eq!(result.furnaces[1].recipe, None);
-        assert_eq!(result.furnaces[1].fuel_type, None);
+        assert!(result.furnaces[1].fuel.is_empty());
+        assert_eq!(result.furnaces[1].status, None);
+    }
+
+    #[test]
+    fn test_parse_electric_furnace() {
+        let json = r#"{"furnaces":[{"name":"electric-furnace","x":0.5,"y":0.5,"recipe":"steel-plate","status":"working"}]}"#;
+        let result: Furnaces = serde_json::from_str(json).unwrap();
+        assert_eq!(result.furnaces[0].fuel_seconds, None);
     }
 
     #[test]
     fn test_parse_no_furnaces() {
-        let json = r#"{"furnaces":[]}"#;
+        let result: Furnaces = serde_json::from_str("{}").unwrap();
+        assert!(result.furnaces.is_empty());
+        assert!(result.groups.is_empty());
+    }
+
+    #[test]
+    fn test_parse_summary_groups() {
+        let json = r#"{"groups":[
+            {"name":"steel-furnace","recipe":"iron-plate","machines":200,
+             "statuses":{"working":150,"no_ingredients":50},
+             "input_items":900,"output_items":40,"fuel_items":1800,"min_fuel_seconds":95.5},
+            {"name":"electric-furnace","recipe":"steel-plate","machines":8,
+             "statuses":{"working":8},"input_items":120,"output_items":3,"fuel_items":0}
+        ]}"#;
         let result: Furnaces = serde_json::from_str(json).unwrap();
         assert!(result.furnaces.is_empty());
+        assert_eq!(result.groups[0].statuses["no_ingredients"], 50);
+        assert_eq!(result.groups[0].min_fuel_seconds, Some(95.5));
+        assert_eq!(result.groups[1].min_fuel_seconds, None);
     }
 }
@@ -0,0 +1,303 @@
+//! Tool for checking whether inserters can keep a machine fed and emptied.
+//!
+//! Finds the crafting machine (or lab) at a position and every inserter whose
+//! drop target (input) or pickup target (output) is that machine, with hand size
+//! from the force's stack size research.
+//!
+//! Throughput is computed on the Rust side from the knowledge base inserter table
+//! (chest-to-chest cycle ticks: burner 102, regular 72, long-handed 52,
+//! fast/stack/bulk 26), so `hand_size × 60 / cycle_ticks` items per second. These
+//! are upper bounds — picking up from a belt is slower than from a chest. The
+//! totals are compared with the item rates the machine's recipe needs at its
+//! effective crafting speed.
+
+use rig::{completion::ToolDefinition, tool::Tool};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::{recipe::RecipeInfo, surface_param};
+use crate::{
+    error::SenseiError,
+    lua,
+    rcon_ext::{execute_lua_json, SharedRcon},
+};
+
+/// Chest-to-chest cycle ticks for an inserter, from the knowledge base table.
+fn cycle_ticks(name: &str) -> Option<u32> {
+    match name {
+        "burner-inserter" => Some(102),
+        "inserter" => Some(72),
+        "long-handed-inserter" => Some(52),
+        "fast-inserter" | "stack-inserter" | "bulk-inserter" => Some(26),
+        _ => None,
+    }
+}
+
+/// Finds inserters around a machine and compares their throughput with the recipe.
+pub struct GetInserters {
+    pub(crate) rcon: SharedRcon,
+}
+
+impl GetInserters {
+    pub const fn new(rcon: SharedRcon) -> Self {
+        Self { rcon }
+    }
+}
+
+/// Arguments for [`GetInserters`].
+#[derive(Debug, Deserialize)]
+pub struct GetInsertersArgs {
+    /// X coordinate of the machine (any tile it covers).
+    pub x: f64,
+    /// Y coordinate of the machine (any tile it covers).
+    pub y: f64,
+    #[doc = surface_arg_doc!()]
+    pub surface: Option<String>,
+}
+
+/// The inspected machine.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct MachineInfo {
+    /// Entity prototype name (e.g. "assembling-machine-2", "lab").
+    pub name: String,
+    pub x: f64,
+    pub y: f64,
+    /// Crafting speed including modules and beacons (absent for labs).
+    #[serde(default)]
+    pub crafting_speed: Option<f64>,
+    #[serde(default)]
+    pub productivity_bonus: Option<f64>,
+    /// The machine's current recipe.
+    #[serde(default)]
+    pub recipe: Option<RecipeInfo>,
+}
+
+impl MachineInfo {
+    /// Item (not fluid) units per second consumed and produced at full speed.
+    fn item_rates(&self) -> Option<(f64, f64)> {
+        let recipe = self.recipe.as_ref()?;
+        let speed = self.crafting_speed?;
+        if recipe.energy <= 0.0 {
+            return None;
+        }
+        let crafts_per_second = speed / recipe.energy;
+        let input: f64 = recipe
+            .ingredients
+            .iter()
+            .filter(|i| i.kind == "item")
+            .map(|i| i.amount)
+            .sum();
+        let output: f64 = recipe
+            .products
+            .iter()
+            .filter(|p| p.kind == "item")
+            .map(|p| p.amount)
+            .sum();
+        let productivity = 1.0 + self.productivity_bonus.unwrap_or(0.0);
+        Some((
+            input * crafts_per_second,
+            output * crafts_per_second * productivity,
+        ))
+    }
+}
+
+/// An inserter feeding or unloading the machine.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct InserterInfo {
+    /// Entity prototype name (e.g. "fast-inserter", "bulk-inserter").
+    pub name: String,
+    pub x: f64,
+    pub y: f64,
+    /// "input" (drops into the machine) or "output" (takes from it).
+    pub role: String,
+    /// Entity the inserter picks up from.
+    pub pickup: Option<String>,
+    /// Entity the inserter drops into.
+    pub drop: Option<String>,
+    /// Items moved per swing, including stack size research.
+    pub hand_size: u32,
+    /// `defines.entity_status` name (e.g. "working", "waiting_for_source_items").
+    pub status: Option<String>,
+    /// Chest-to-chest items per second, or `None` for inserters missing from the table.
+    #[serde(default)]
+    pub items_per_second: Option<f64>,
+}
+
+impl InserterInfo {
+    fn items_per_second(&self) -> Option<f64> {
+        cycle_ticks(&self.name).map(|ticks| f64::from(self.hand_size) * 60.0 / f64::from(ticks))
+    }
+}
+
+/// Inserter capacity on one side of the machine against what the recipe needs.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ThroughputCheck {
+    /// Items per second the recipe needs moved on this side.
+    pub required_per_second: f64,
+    /// Sum of the inserters' chest-to-chest rates.
+    pub capacity_per_second: f64,
+    pub inserters: u32,
+    /// Whether the inserters keep up with the machine.
+    pub sufficient: bool,
+}
+
+fn check(required: f64, inserters: &[&InserterInfo]) -> ThroughputCheck {
+    let capacity: f64 = inserters.iter().filter_map(|i| i.items_per_second).sum();
+    ThroughputCheck {
+        required_per_second: required,
+        capacity_per_second: capacity,
+        inserters: u32::try_from(inserters.len()).unwrap_or(u32::MAX),
+        sufficient: capacity >= required,
+    }
+}
+
+/// Top-level response: machine, its inserters, and per-side throughput checks.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct InserterAnalysis {
+    pub machine: MachineInfo,
+    #[serde(default)]
+    pub inserters: Vec<InserterInfo>,
+    /// Feeding side, when the machine has a recipe with item ingredients.
+    #[serde(default)]
+    pub input: Option<ThroughputCheck>,
+    /// Unloading side, when the machine has a recipe with item products.
+    #[serde(default)]
+    pub output: Option<ThroughputCheck>,
+}
+
+impl InserterAnalysis {
+    fn analyze(&mut self) {
+        for inserter in &mut self.inserters {
+            inserter.items_per_second = inserter.items_per_second();
+        }
+        let Some((input, output)) = self.machine.item_rates() else {
+            return;
+        };
+        let side = |role: &str| -> Vec<&InserterInfo> {
+            self.inserters.iter().filter(|i| i.role == role).collect()
+        };
+        self.input = (input > 0.0).then(|| check(input, &side("input")));
+        self.output = (output > 0.0).then(|| check(output, &side("output")));
+    }
+}
+
+impl Tool for GetInserters {
+    const NAME: &'static str = "get_inserters";
+    type Error = SenseiError;
+    type Args = GetInsertersArgs;
+    type Output = InserterAnalysis;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "get_inserters".to_string(),
+            description: "Inspect the inserters feeding and unloading the machine at a position: inserter type, hand size with stack bonuses, pickup/drop targets, status, and theoretical items/s. Compares total inserter throughput on each side with what the machine's recipe needs at its crafting speed.".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "x": {
+                        "type": "number",
+                        "description": "X coordinate of the machine (e.g. from get_assemblers)"
+                    },
+                    "y": {
+                        "type": "number",
+                        "description": "Y coordinate of the machine"
+                    },
+                    "surface": surface_param()
+                },
+                "required": ["x", "y"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let lua = lua::inserters_around(args.x, args.y, args.surface.as_deref());
+        let json = execute_lua_json(&self.rcon, &lua).await?;
+        let mut result: InserterAnalysis = serde_json::from_str(&json)?;
+        result.analyze();
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analysis(json: &str) -> InserterAnalysis {
+        let mut a: InserterAnalysis = serde_json::from_str(json).unwrap();
+        a.analyze();
+        a
+    }
+
+    #[test]
+    fn test_cycle_table() {
+        assert_eq!(cycle_ticks("burner-inserter"), Some(102));
+        assert_eq!(cycle_ticks("inserter"), Some(72));
+        assert_eq!(cycle_ticks("long-handed-inserter"), Some(52));
+        assert_eq!(cycle_ticks("bulk-inserter"), Some(26));
+        assert_eq!(cycle_ticks("modded-inserter"), None);
+    }
+
+    #[test]
+    fn test_fast_circuit_assembler_is_starved() {
+        // Electronic circuit in an AM2 (speed 0.75, 0.5 s): 1.5 crafts/s needs 1.5 plates
+        // + 4.5 cables = 6 items/s in. One regular inserter with hand 1 moves 0.83/s.
+        let a = analysis(
+            r#"{"machine":{"name":"assembling-machine-2","x":0.5,"y":0.5,"crafting_speed":0.75,
+                 "productivity_bonus":0,
+                 "recipe":{"name":"electronic-circuit","energy":0.5,
+                   "ingredients":[{"name":"iron-plate","type":"item","amount":1},{"name":"copper-cable","type":"item","amount":3}],
+                   "products":[{"name":"electronic-circuit","type":"item","amount":1}]}},
+               "inserters":[
+                 {"name":"inserter","x":-1.5,"y":0.5,"role":"input","pickup":"transport-belt","drop":"assembling-machine-2","hand_size":1,"status":"working"},
+                 {"name":"fast-inserter","x":2.5,"y":0.5,"role":"output","pickup":"assembling-machine-2","drop":"wooden-chest","hand_size":1,"status":"working"}
+               ]}"#,
+        );
+        let input = a.input.unwrap();
+        assert!((input.required_per_second - 6.0).abs() < 1e-9);
+        assert!((input.capacity_per_second - 60.0 / 72.0).abs() < 1e-9);
+        assert!(!input.sufficient);
+        let output = a.output.unwrap();
+        assert!((output.required_per_second - 1.5).abs() < 1e-9);
+        assert!(output.sufficient);
+    }
+
+    #[test]
+    fn test_fluid_ingredients_are_ignored() {
+        let a = analysis(
+            r#"{"machine":{"name":"chemical-plant","x":0,"y":0,"crafting_speed":1,"productivity_bonus":0.1,
+                 "recipe":{"name":"plastic-bar","energy":1,
+                   "ingredients":[{"name":"petroleum-gas","type":"fluid","amount":20},{"name":"coal","type":"item","amount":1}],
+                   "products":[{"name":"plastic-bar","type":"item","amount":2}]}},
+               "inserters":[{"name":"bulk-inserter","x":2,"y":0,"role":"output","pickup":"chemical-plant","hand_size":4}]}"#,
+        );
+        assert!((a.input.unwrap().required_per_second - 1.0).abs() < 1e-9);
+        let output = a.output.unwrap();
+        assert!((output.required_per_second - 2.2).abs() < 1e-9);
+        assert!((output.capacity_per_second - 4.0 * 60.0 / 26.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_lab_has_no_recipe_check() {
+        let a = analysis(
+            r#"{"machine":{"name":"lab","x":0,"y":0},
+               "inserters":[{"name":"inserter","x":2,"y":0,"role":"input","hand_size":1}]}"#,
+        );
+        assert_eq!(a.input, None);
+        assert_eq!(a.inserters[0].items_per_second, Some(60.0 / 72.0));
+    }
+
+    #[test]
+    fn test_machine_without_inserters_cannot_keep_up() {
+        let a = analysis(
+            r#"{"machine":{"name":"assembling-machine-1","x":0.5,"y":0.5,"crafting_speed":0.5,
+                 "productivity_bonus":0,
+                 "recipe":{"name":"iron-gear-wheel","energy":0.5,
+                   "ingredients":[{"name":"iron-plate","type":"item","amount":2}],
+                   "products":[{"name":"iron-gear-wheel","type":"item","amount":1}]}}}"#,
+        );
+        assert!(a.inserters.is_empty());
+        let input = a.input.unwrap();
+        assert_eq!(input.inserters, 0);
+        assert!(!input.sufficient);
+    }
+}
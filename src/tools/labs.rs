@@ -0,0 +1,223 @@
+//! Tool for inspecting labs and finding the science pack that holds research back.
+//!
+//! Finds the force's `type="lab"` entities (including biolabs) and checks each
+//! lab's pack inventory against the current research's ingredients. A lab
+//! missing any of them is starved — it cannot consume the others either.
+//!
+//! The summary covers every lab on the surface. The limiting pack is picked on
+//! the Rust side: the pack the most labs are out of, or, when no lab is out yet,
+//! the one with the fewest research units left in stock.
+
+use std::collections::BTreeMap;
+
+use rig::{completion::ToolDefinition, tool::Tool};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::{inventory::InventoryItem, surface_param};
+use crate::{
+    error::SenseiError,
+    lua,
+    rcon_ext::{execute_lua_json, SharedRcon},
+};
+
+/// Lists labs with their science packs and summarizes which pack is limiting.
+pub struct GetLabs {
+    pub(crate) rcon: SharedRcon,
+}
+
+impl GetLabs {
+    pub const fn new(rcon: SharedRcon) -> Self {
+        Self { rcon }
+    }
+}
+
+/// Arguments for [`GetLabs`]. All optional.
+#[derive(Debug, Deserialize)]
+pub struct GetLabsArgs {
+    /// Max labs to list individually. Defaults to 20. The summary always covers every lab.
+    pub limit: Option<u32>,
+    #[doc = surface_arg_doc!()]
+    pub surface: Option<String>,
+}
+
+/// A single lab's state snapshot.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct LabInfo {
+    /// Entity prototype name (e.g. "lab", "biolab").
+    pub name: String,
+    /// World x coordinate.
+    pub x: f64,
+    /// World y coordinate.
+    pub y: f64,
+    /// `defines.entity_status` name (e.g. "working", "missing_science_packs", "no_power").
+    pub status: Option<String>,
+    /// Science packs loaded in the lab.
+    #[serde(default)]
+    pub packs: Vec<InventoryItem>,
+    /// Installed modules.
+    #[serde(default)]
+    pub modules: Vec<InventoryItem>,
+    /// Current research packs this lab has none of.
+    #[serde(default)]
+    pub missing: Vec<String>,
+    /// Whether the lab is missing any pack needed by the current research.
+    pub starved: bool,
+}
+
+/// Supply of one current-research pack across all labs.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct LabPack {
+    /// Science pack name.
+    pub name: String,
+    /// Packs consumed per research unit.
+    pub amount: u32,
+    /// Packs loaded across all labs.
+    pub stock: u64,
+    /// Labs holding none of this pack.
+    pub labs_without: u32,
+}
+
+impl LabPack {
+    /// Research units the loaded stock covers.
+    fn units_in_stock(&self) -> f64 {
+        self.stock as f64 / f64::from(self.amount.max(1))
+    }
+}
+
+/// Totals over every lab on the surface.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct LabSummary {
+    /// Total labs on the surface.
+    pub labs: u32,
+    /// Labs missing at least one current research pack.
+    pub starved: u32,
+    /// Lab count per `defines.entity_status` name.
+    #[serde(default)]
+    pub statuses: BTreeMap<String, u32>,
+    /// Current research packs with stock and shortage counts.
+    #[serde(default)]
+    pub packs: Vec<LabPack>,
+    /// The pack holding research back, if any research is active.
+    #[serde(default)]
+    pub limiting_pack: Option<String>,
+}
+
+impl LabSummary {
+    /// The pack the most labs are out of, tie-broken (and, when none is out,
+    /// decided) by the fewest research units left in stock.
+    fn limiting_pack(&self) -> Option<String> {
+        self.packs
+            .iter()
+            .min_by(|a, b| {
+                b.labs_without
+                    .cmp(&a.labs_without)
+                    .then(a.units_in_stock().total_cmp(&b.units_in_stock()))
+            })
+            .map(|p| p.name.clone())
+    }
+}
+
+/// Top-level response: current research, individual labs, and the summary.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct Labs {
+    /// Technology being researched, or `None` if research is idle.
+    #[serde(default)]
+    pub research: Option<String>,
+    #[serde(default)]
+    pub labs: Vec<LabInfo>,
+    pub summary: LabSummary,
+}
+
+impl Tool for GetLabs {
+    const NAME: &'static str = "get_labs";
+    type Error = SenseiError;
+    type Args = GetLabsArgs;
+    type Output = Labs;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "get_labs".to_string(),
+            description: "Get labs with their science pack inventories, status, modules, and whether each is starved of a pack the current research needs. The summary covers every lab with status counts, per-pack stock, how many labs are out of each pack, and the limiting pack.".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of labs to list individually (default: 20). The summary always includes every lab."
+                    },
+                    "surface": surface_param()
+                }
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let limit = args.limit.unwrap_or(20);
+        let lua = lua::labs(limit, args.surface.as_deref());
+        let json = execute_lua_json(&self.rcon, &lua).await?;
+        let mut result: Labs = serde_json::from_str(&json)?;
+        result.summary.limiting_pack = result.summary.limiting_pack();
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(json: &str) -> LabSummary {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_parse_labs() {
+        let json = r#"{"research":"logistics-2","labs":[
+            {"name":"lab","x":4.5,"y":8.5,"status":"working",
+             "packs":[{"name":"automation-science-pack","count":12},{"name":"logistic-science-pack","count":3}],
+             "modules":[{"name":"speed-module","count":2}],"starved":false},
+            {"name":"lab","x":7.5,"y":8.5,"status":"missing_science_packs",
+             "packs":[{"name":"automation-science-pack","count":40}],
+             "missing":["logistic-science-pack"],"starved":true}
+        ],"summary":{"labs":2,"starved":1,"statuses":{"working":1,"missing_science_packs":1},
+            "packs":[{"name":"automation-science-pack","amount":1,"stock":52,"labs_without":0},
+                     {"name":"logistic-science-pack","amount":1,"stock":3,"labs_without":1}]}}"#;
+        let result: Labs = serde_json::from_str(json).unwrap();
+        assert_eq!(result.research.as_deref(), Some("logistics-2"));
+        assert!(result.labs[1].starved);
+        assert_eq!(result.labs[1].missing, vec!["logistic-science-pack"]);
+        assert_eq!(result.labs[0].modules[0].count, 2);
+        assert_eq!(result.summary.statuses["missing_science_packs"], 1);
+    }
+
+    #[test]
+    fn test_parse_idle_research_without_labs() {
+        let json = r#"{"summary":{"labs":0,"starved":0}}"#;
+        let result: Labs = serde_json::from_str(json).unwrap();
+        assert_eq!(result.research, None);
+        assert!(result.labs.is_empty());
+        assert!(result.summary.packs.is_empty());
+        assert_eq!(result.summary.limiting_pack(), None);
+    }
+
+    #[test]
+    fn test_limiting_pack_most_labs_without() {
+        let s = summary(
+            r#"{"labs":10,"starved":6,"packs":[
+                {"name":"automation-science-pack","amount":1,"stock":5,"labs_without":2},
+                {"name":"chemical-science-pack","amount":1,"stock":90,"labs_without":6}]}"#,
+        );
+        assert_eq!(s.limiting_pack().as_deref(), Some("chemical-science-pack"));
+    }
+
+    #[test]
+    fn test_limiting_pack_lowest_units_in_stock() {
+        // Nobody is out yet: 30 military packs cover 15 units at 2 per unit, fewer than 20 red
+        let s = summary(
+            r#"{"labs":4,"starved":0,"packs":[
+                {"name":"automation-science-pack","amount":1,"stock":20,"labs_without":0},
+                {"name":"military-science-pack","amount":2,"stock":30,"labs_without":0}]}"#,
+        );
+        assert_eq!(s.limiting_pack().as_deref(), Some("military-science-pack"));
+    }
+}
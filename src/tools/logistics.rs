@@ -0,0 +1,234 @@
+//! Tool for inspecting logistic networks: robots, roboports, storage, and requests.
+//!
+//! Reads `force.logistic_networks` for the target surface. Per network it reports
+//! available/total construction and logistic robots, roboport count, the
+//! charging queue, the largest storage stacks, and requests from requester/buffer
+//! chests and players that robots are not covering.
+//!
+//! Unfulfilled requests are grouped per item on the Rust side, with the reason:
+//! the item is missing from the network, or it is there but no logistic robot is
+//! free to carry it.
+
+use std::{cmp::Reverse, collections::BTreeMap};
+
+use rig::{completion::ToolDefinition, tool::Tool};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::{inventory::InventoryItem, surface_param};
+use crate::{
+    error::SenseiError,
+    lua,
+    rcon_ext::{execute_lua_json, SharedRcon},
+};
+
+/// Lists logistic networks with robot counts, storage, and unfulfilled requests.
+pub struct GetLogisticNetworks {
+    pub(crate) rcon: SharedRcon,
+}
+
+impl GetLogisticNetworks {
+    pub const fn new(rcon: SharedRcon) -> Self {
+        Self { rcon }
+    }
+}
+
+/// Arguments for [`GetLogisticNetworks`]. All optional.
+#[derive(Debug, Deserialize)]
+pub struct GetLogisticNetworksArgs {
+    #[doc = surface_arg_doc!()]
+    pub surface: Option<String>,
+}
+
+/// One requester slot that is below its requested count.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct UnfulfilledRequest {
+    /// Requesting entity (e.g. "requester-chest", "buffer-chest", "character").
+    pub requester: String,
+    pub x: f64,
+    pub y: f64,
+    /// Requested item name.
+    pub item: String,
+    /// Requested count.
+    pub requested: u64,
+    /// Count already in the requester.
+    pub present: u64,
+    /// Count robots are currently delivering.
+    pub en_route: u64,
+    /// Count of the item available in the whole network.
+    pub in_network: u64,
+}
+
+/// Unfulfilled requests for one item across a network.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ItemShortage {
+    pub item: String,
+    /// Items still missing after deliveries in flight.
+    pub missing: u64,
+    /// Number of requesters short of this item.
+    pub requesters: u32,
+    pub in_network: u64,
+    /// Why robots are not delivering: "not_in_network" or "no_free_robots".
+    pub reason: Option<String>,
+}
+
+/// A single logistic network's state.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct LogisticNetworkInfo {
+    /// Network id.
+    pub id: u32,
+    /// Position of one of the network's roboports, to tell networks apart.
+    pub x: Option<f64>,
+    pub y: Option<f64>,
+    pub roboports: u32,
+    pub available_construction_robots: u32,
+    pub total_construction_robots: u32,
+    pub available_logistic_robots: u32,
+    pub total_logistic_robots: u32,
+    /// Robots charging at roboports right now.
+    pub charging_robots: u32,
+    /// Robots queued waiting for a charging slot.
+    pub waiting_to_charge: u32,
+    /// Largest item stacks stored in the network (up to 30).
+    #[serde(default)]
+    pub storage: Vec<InventoryItem>,
+    /// Requester slots below their requested count (up to 30).
+    #[serde(default)]
+    pub unfulfilled: Vec<UnfulfilledRequest>,
+    /// `unfulfilled` grouped per item, most missing first.
+    #[serde(default)]
+    pub shortages: Vec<ItemShortage>,
+}
+
+impl LogisticNetworkInfo {
+    /// Group unfulfilled requests by item and explain each shortage.
+    fn shortages(&self) -> Vec<ItemShortage> {
+        let mut by_item: BTreeMap<&str, ItemShortage> = BTreeMap::new();
+        for req in &self.unfulfilled {
+            let entry = by_item
+                .entry(req.item.as_str())
+                .or_insert_with(|| ItemShortage {
+                    item: req.item.clone(),
+                    missing: 0,
+                    requesters: 0,
+                    in_network: req.in_network,
+                    reason: None,
+                });
+            entry.missing += req.requested.saturating_sub(req.present + req.en_route);
+            entry.requesters += 1;
+        }
+        let mut shortages: Vec<ItemShortage> = by_item.into_values().collect();
+        for s in &mut shortages {
+            s.reason = if s.in_network == 0 {
+                Some("not_in_network".to_string())
+            } else if self.available_logistic_robots == 0 {
+                Some("no_free_robots".to_string())
+            } else {
+                None
+            };
+        }
+        shortages.sort_by_key(|s| Reverse(s.missing));
+        shortages
+    }
+}
+
+/// Top-level response with one entry per logistic network on the surface.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct LogisticNetworks {
+    #[serde(default)]
+    pub networks: Vec<LogisticNetworkInfo>,
+}
+
+impl Tool for GetLogisticNetworks {
+    const NAME: &'static str = "get_logistic_networks";
+    type Error = SenseiError;
+    type Args = GetLogisticNetworksArgs;
+    type Output = LogisticNetworks;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "get_logistic_networks".to_string(),
+            description: "Get logistic networks with available/total construction and logistic robots, roboport count, robots charging or queued to charge, largest storage contents, and unfulfilled requests from requester chests and players — grouped per item with the reason (item not in network, or no free robots).".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "surface": surface_param()
+                }
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let lua = lua::logistic_networks(args.surface.as_deref());
+        let json = execute_lua_json(&self.rcon, &lua).await?;
+        let mut result: LogisticNetworks = serde_json::from_str(&json)?;
+        for network in &mut result.networks {
+            network.shortages = network.shortages();
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn network(json: &str) -> LogisticNetworkInfo {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_parse_networks() {
+        let json = r#"{"networks":[
+            {"id":1,"x":0,"y":0,"roboports":12,"available_construction_robots":40,"total_construction_robots":50,
+             "available_logistic_robots":0,"total_logistic_robots":80,"charging_robots":8,"waiting_to_charge":14,
+             "storage":[{"name":"iron-plate","count":4800},{"name":"copper-cable","count":1200}]}
+        ]}"#;
+        let result: LogisticNetworks = serde_json::from_str(json).unwrap();
+        let n = &result.networks[0];
+        assert_eq!(n.roboports, 12);
+        assert_eq!(n.waiting_to_charge, 14);
+        assert_eq!(n.storage[0].name, "iron-plate");
+        assert!(n.unfulfilled.is_empty());
+        assert!(n.shortages().is_empty());
+    }
+
+    #[test]
+    fn test_parse_no_networks() {
+        let result: LogisticNetworks = serde_json::from_str("{}").unwrap();
+        assert!(result.networks.is_empty());
+    }
+
+    #[test]
+    fn test_shortages_grouped_by_item() {
+        let n = network(
+            r#"{"id":1,"roboports":4,"available_construction_robots":10,"total_construction_robots":10,
+                "available_logistic_robots":5,"total_logistic_robots":20,"charging_robots":0,"waiting_to_charge":0,
+                "unfulfilled":[
+                  {"requester":"requester-chest","x":1,"y":1,"item":"electronic-circuit","requested":200,"present":50,"en_route":0,"in_network":0},
+                  {"requester":"requester-chest","x":2,"y":1,"item":"electronic-circuit","requested":200,"present":100,"en_route":50,"in_network":0},
+                  {"requester":"character","x":9,"y":4,"item":"transport-belt","requested":100,"present":80,"en_route":0,"in_network":400}
+                ]}"#,
+        );
+        let shortages = n.shortages();
+        assert_eq!(shortages.len(), 2);
+        assert_eq!(shortages[0].item, "electronic-circuit");
+        assert_eq!(shortages[0].missing, 200);
+        assert_eq!(shortages[0].requesters, 2);
+        assert_eq!(shortages[0].reason.as_deref(), Some("not_in_network"));
+        assert_eq!(shortages[1].missing, 20);
+        assert_eq!(shortages[1].reason, None);
+    }
+
+    #[test]
+    fn test_shortage_without_free_robots() {
+        let n = network(
+            r#"{"id":2,"roboports":2,"available_construction_robots":0,"total_construction_robots":0,
+                "available_logistic_robots":0,"total_logistic_robots":30,"charging_robots":10,"waiting_to_charge":20,
+                "unfulfilled":[
+                  {"requester":"requester-chest","x":1,"y":1,"item":"iron-gear-wheel","requested":100,"present":0,"en_route":0,"in_network":900}
+                ]}"#,
+        );
+        assert_eq!(n.shortages()[0].reason.as_deref(), Some("no_free_robots"));
+    }
+}
@@ -7,24 +7,76 @@
 //!
 //! Tools are read-only — they observe the game but never execute actions.
 
+/// Doc line for the optional `surface` argument of the factory tools, used as
+/// `#[doc = surface_arg_doc!()]`.
+macro_rules! surface_arg_doc {
+    () => {
+        "Surface (planet or space platform) to query. Defaults to the player's current surface."
+    };
+}
+
+mod alerts;
 mod assemblers;
+mod belts;
+mod construction;
+mod defense;
+mod drills;
 mod entities;
+mod find_item;
+mod fluid_production;
+mod fluid_system;
 mod furnaces;
+mod inserters;
 mod inventory;
+mod labs;
+mod logistics;
+mod oil_balance;
+mod overview;
+mod pollution;
 mod position;
 mod power;
 mod production;
 mod recipe;
 mod research;
 mod resources;
+mod surfaces;
+mod technology;
+mod train_stops;
+mod trains;
 
+pub use alerts::{GetAlerts, GetAlertsArgs};
 pub use assemblers::{GetAssemblers, GetAssemblersArgs};
+pub use belts::{GetBelts, GetBeltsArgs};
+pub use construction::{GetConstructionBacklog, GetConstructionBacklogArgs};
+pub use defense::{GetDefense, GetDefenseArgs};
+pub use drills::{GetMiningDrills, GetMiningDrillsArgs};
 pub use entities::{GetNearbyEntities, GetNearbyEntitiesArgs};
+pub use find_item::{FindItem, FindItemArgs};
+pub use fluid_production::{GetFluidProduction, GetFluidProductionArgs};
+pub use fluid_system::{GetFluidSystem, GetFluidSystemArgs};
 pub use furnaces::{GetFurnaces, GetFurnacesArgs};
+pub use inserters::{GetInserters, GetInsertersArgs};
 pub use inventory::{GetPlayerInventory, GetPlayerInventoryArgs};
+pub use labs::{GetLabs, GetLabsArgs};
+pub use logistics::{GetLogisticNetworks, GetLogisticNetworksArgs};
+pub use oil_balance::{SolveOilBalance, SolveOilBalanceArgs};
+pub use overview::{GetProductionOverview, GetProductionOverviewArgs};
+pub use pollution::{GetPollution, GetPollutionArgs};
 pub use position::{GetPlayerPosition, GetPlayerPositionArgs};
 pub use power::{GetPowerStats, GetPowerStatsArgs};
-pub use production::{GetProductionStats, GetProductionStatsArgs};
+pub use production::{FlowWindow, GetProductionStats, GetProductionStatsArgs};
 pub use recipe::{GetRecipe, GetRecipeArgs};
 pub use research::{GetResearchStatus, GetResearchStatusArgs};
 pub use resources::{GetNearbyResources, GetNearbyResourcesArgs};
+pub use surfaces::{ListSurfaces, ListSurfacesArgs};
+pub use technology::{GetTechnology, GetTechnologyArgs};
+pub use train_stops::{GetTrainStops, GetTrainStopsArgs};
+pub use trains::{GetTrains, GetTrainsArgs};
+
+/// JSON schema of the optional `surface` argument shared by the factory tools.
+fn surface_param() -> serde_json::Value {
+    serde_json::json!({
+        "type": "string",
+        "description": "Surface to query, e.g. 'nauvis', 'vulcanus', or a space platform (default: the player's current surface)"
+    })
+}
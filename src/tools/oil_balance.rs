@@ -0,0 +1,651 @@
+//! Tool for balancing oil processing without reading the map.
+//!
+//! Given how many refineries run each oil recipe, how many chemical plants crack
+//! heavy and light oil, and which recipes consume the products, this computes the
+//! steady-state heavy oil, light oil, and petroleum gas flows. Recipe amounts and
+//! crafting times come from the game through [`GetRecipe`], so modded or changed
+//! recipes are respected; the balance itself is pure math on the Rust side.
+//!
+//! The model follows how oil setups behave in practice: consumers take what they
+//! need first, crackers take what is left (up to their capacity), and anything
+//! still left over backs up — which eventually stops the refineries.
+
+use std::collections::BTreeMap;
+
+use rig::{completion::ToolDefinition, tool::Tool};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::recipe::{GetRecipe, GetRecipeArgs, RecipeInfo};
+use crate::{error::SenseiError, rcon_ext::SharedRcon};
+
+const HEAVY_OIL: &str = "heavy-oil";
+const LIGHT_OIL: &str = "light-oil";
+const PETROLEUM_GAS: &str = "petroleum-gas";
+const OIL_FLUIDS: [&str; 3] = [HEAVY_OIL, LIGHT_OIL, PETROLEUM_GAS];
+
+const ADVANCED_OIL_PROCESSING: &str = "advanced-oil-processing";
+const BASIC_OIL_PROCESSING: &str = "basic-oil-processing";
+const COAL_LIQUEFACTION: &str = "coal-liquefaction";
+const HEAVY_OIL_CRACKING: &str = "heavy-oil-cracking";
+const LIGHT_OIL_CRACKING: &str = "light-oil-cracking";
+
+/// Flows smaller than this (units/s) count as balanced.
+const EPSILON: f64 = 1e-6;
+
+/// Computes oil processing balance and cracking ratios from machine counts.
+pub struct SolveOilBalance {
+    pub(crate) rcon: SharedRcon,
+}
+
+impl SolveOilBalance {
+    pub const fn new(rcon: SharedRcon) -> Self {
+        Self { rcon }
+    }
+}
+
+/// A recipe that consumes oil products, and how many machines run it.
+#[derive(Debug, Deserialize)]
+pub struct OilConsumer {
+    /// Recipe prototype name (e.g. "plastic-bar", "lubricant", "sulfur").
+    pub recipe: String,
+    /// Machines running the recipe.
+    pub machines: u32,
+    /// Crafting speed of those machines. Defaults to `chemical_plant_speed`.
+    pub crafting_speed: Option<f64>,
+}
+
+/// Arguments for [`SolveOilBalance`]. All optional.
+#[derive(Debug, Deserialize)]
+pub struct SolveOilBalanceArgs {
+    /// Refineries running advanced oil processing.
+    pub advanced_refineries: Option<u32>,
+    /// Refineries running basic oil processing.
+    pub basic_refineries: Option<u32>,
+    /// Refineries running coal liquefaction.
+    pub coal_liquefaction: Option<u32>,
+    /// Chemical plants cracking heavy oil into light oil.
+    pub heavy_oil_crackers: Option<u32>,
+    /// Chemical plants cracking light oil into petroleum gas.
+    pub light_oil_crackers: Option<u32>,
+    /// Recipes consuming oil products.
+    pub consumers: Option<Vec<OilConsumer>>,
+    /// Crafting speed of the refineries. Defaults to 1.0 (an oil refinery without modules).
+    pub refinery_speed: Option<f64>,
+    /// Crafting speed of the chemical plants. Defaults to 1.0 (a chemical plant without modules).
+    pub chemical_plant_speed: Option<f64>,
+}
+
+/// A recipe running in some number of machines.
+struct RecipeRun<'a> {
+    recipe: &'a RecipeInfo,
+    machines: f64,
+    crafting_speed: f64,
+}
+
+impl RecipeRun<'_> {
+    fn crafts_per_second(&self) -> f64 {
+        if self.recipe.energy > 0.0 {
+            self.machines * self.crafting_speed / self.recipe.energy
+        } else {
+            0.0
+        }
+    }
+
+    /// Units of `name` consumed per second.
+    fn input(&self, name: &str) -> f64 {
+        let per_craft: f64 = self
+            .recipe
+            .ingredients
+            .iter()
+            .filter(|i| i.name == name)
+            .map(|i| i.amount)
+            .sum();
+        per_craft * self.crafts_per_second()
+    }
+
+    /// Units of `name` produced per second.
+    fn output(&self, name: &str) -> f64 {
+        let per_craft: f64 = self
+            .recipe
+            .products
+            .iter()
+            .filter(|p| p.name == name)
+            .map(|p| p.amount)
+            .sum();
+        per_craft * self.crafts_per_second()
+    }
+
+    /// Units of `to` produced per unit of `from` consumed, from the recipe amounts.
+    fn conversion(&self, from: &str, to: &str) -> f64 {
+        let unit = RecipeRun {
+            recipe: self.recipe,
+            machines: 1.0,
+            crafting_speed: self.crafting_speed,
+        };
+        let input = unit.input(from);
+        if input > 0.0 {
+            unit.output(to) / input
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Steady-state flow of one oil product.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct FluidFlow {
+    pub name: String,
+    /// Units per second made by refineries and crackers.
+    pub produced: f64,
+    /// Units per second actually consumed (by recipes and crackers).
+    pub consumed: f64,
+    /// Units per second nothing takes — this backs up.
+    pub surplus: f64,
+    /// Units per second the consumers want but do not get.
+    pub shortfall: f64,
+    /// "balanced", "backs_up", or "short".
+    pub status: String,
+}
+
+/// Cracking that leaves no heavy or light oil over for the given refineries and consumers.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct CrackingPlan {
+    pub heavy_oil_crackers: u32,
+    pub light_oil_crackers: u32,
+    /// Petroleum gas per second with that cracking.
+    pub petroleum_gas_per_second: f64,
+    /// Products still short with that cracking, units per second. More refineries are needed.
+    #[serde(default)]
+    pub shortfall: BTreeMap<String, f64>,
+}
+
+/// Top-level response: oil product flows, inputs, and cracking advice.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct OilBalance {
+    /// Heavy oil, light oil, and petroleum gas, in cracking order.
+    pub fluids: Vec<FluidFlow>,
+    /// Other inputs of refineries and crackers (crude oil, water, coal, steam), units per second.
+    pub inputs: BTreeMap<String, f64>,
+    pub recommended: CrackingPlan,
+    /// Plain-language problems with the current setup.
+    pub issues: Vec<String>,
+}
+
+/// Split `produced` between direct `demand` first, then up to `crack_capacity` of cracking.
+///
+/// Returns the flow and the units per second sent to the crackers.
+fn allocate(name: &str, produced: f64, demand: f64, crack_capacity: f64) -> (FluidFlow, f64) {
+    let used = produced.min(demand);
+    let cracked = (produced - used).min(crack_capacity);
+    let surplus = produced - used - cracked;
+    let shortfall = demand - used;
+    let status = if surplus > EPSILON {
+        "backs_up"
+    } else if shortfall > EPSILON {
+        "short"
+    } else {
+        "balanced"
+    };
+    let flow = FluidFlow {
+        name: name.to_string(),
+        produced,
+        consumed: used + cracked,
+        surplus,
+        shortfall,
+        status: status.to_string(),
+    };
+    (flow, cracked)
+}
+
+/// Run heavy → light → petroleum through [`allocate`] with the given cracking capacity.
+///
+/// Returns the three flows and the heavy and light oil sent to the crackers.
+fn cascade(
+    produced: &dyn Fn(&str) -> f64,
+    demand: &dyn Fn(&str) -> f64,
+    heavy_cracking: &RecipeRun,
+    light_cracking: &RecipeRun,
+    capacity: (f64, f64),
+) -> (Vec<FluidFlow>, f64, f64) {
+    let (heavy, heavy_cracked) = allocate(
+        HEAVY_OIL,
+        produced(HEAVY_OIL),
+        demand(HEAVY_OIL),
+        capacity.0,
+    );
+    let light_made =
+        produced(LIGHT_OIL) + heavy_cracked * heavy_cracking.conversion(HEAVY_OIL, LIGHT_OIL);
+    let (light, light_cracked) = allocate(LIGHT_OIL, light_made, demand(LIGHT_OIL), capacity.1);
+    let gas_made = produced(PETROLEUM_GAS)
+        + light_cracked * light_cracking.conversion(LIGHT_OIL, PETROLEUM_GAS);
+    let (gas, _) = allocate(PETROLEUM_GAS, gas_made, demand(PETROLEUM_GAS), 0.0);
+    (vec![heavy, light, gas], heavy_cracked, light_cracked)
+}
+
+/// Machines needed to crack `rate` units per second of `input`.
+fn crackers_for(rate: f64, input: &str, cracking: &RecipeRun) -> u32 {
+    let per_machine = RecipeRun {
+        recipe: cracking.recipe,
+        machines: 1.0,
+        crafting_speed: cracking.crafting_speed,
+    }
+    .input(input);
+    if per_machine > 0.0 && rate > EPSILON {
+        (rate / per_machine).ceil() as u32
+    } else {
+        0
+    }
+}
+
+/// Steady-state oil balance for refineries, crackers, and consumers.
+fn solve(
+    refining: &[RecipeRun],
+    heavy_cracking: &RecipeRun,
+    light_cracking: &RecipeRun,
+    consumers: &[RecipeRun],
+) -> OilBalance {
+    let produced = |name: &str| refining.iter().map(|r| r.output(name)).sum::<f64>();
+    let demand = |name: &str| {
+        refining
+            .iter()
+            .chain(consumers)
+            .map(|r| r.input(name))
+            .sum::<f64>()
+    };
+    let capacity = (
+        heavy_cracking.input(HEAVY_OIL),
+        light_cracking.input(LIGHT_OIL),
+    );
+    let (fluids, heavy_cracked, light_cracked) =
+        cascade(&produced, &demand, heavy_cracking, light_cracking, capacity);
+
+    let mut inputs = BTreeMap::new();
+    let mut add_inputs = |run: &RecipeRun, fraction: f64| {
+        for ingredient in &run.recipe.ingredients {
+            if !OIL_FLUIDS.contains(&ingredient.name.as_str()) {
+                *inputs.entry(ingredient.name.clone()).or_insert(0.0) +=
+                    run.input(&ingredient.name) * fraction;
+            }
+        }
+    };
+    for run in refining {
+        add_inputs(run, 1.0);
+    }
+    if capacity.0 > 0.0 {
+        add_inputs(heavy_cracking, heavy_cracked / capacity.0);
+    }
+    if capacity.1 > 0.0 {
+        add_inputs(light_cracking, light_cracked / capacity.1);
+    }
+
+    let (ideal, heavy_needed, light_needed) = cascade(
+        &produced,
+        &demand,
+        heavy_cracking,
+        light_cracking,
+        (f64::INFINITY, f64::INFINITY),
+    );
+    let recommended = CrackingPlan {
+        heavy_oil_crackers: crackers_for(heavy_needed, HEAVY_OIL, heavy_cracking),
+        light_oil_crackers: crackers_for(light_needed, LIGHT_OIL, light_cracking),
+        petroleum_gas_per_second: ideal[2].produced,
+        shortfall: ideal
+            .iter()
+            .filter(|f| f.shortfall > EPSILON)
+            .map(|f| (f.name.clone(), f.shortfall))
+            .collect(),
+    };
+
+    let issues = diagnose(&fluids, &recommended, heavy_cracking, light_cracking);
+    OilBalance {
+        fluids,
+        inputs,
+        recommended,
+        issues,
+    }
+}
+
+/// Explain backed-up and short products, with the cracking that would fix them.
+fn diagnose(
+    fluids: &[FluidFlow],
+    plan: &CrackingPlan,
+    heavy_cracking: &RecipeRun,
+    light_cracking: &RecipeRun,
+) -> Vec<String> {
+    let mut issues = Vec::new();
+    for flow in fluids {
+        match flow.status.as_str() {
+            "backs_up" => {
+                let mut issue = format!(
+                    "{} backs up by {:.1}/s — once its tanks fill, the refineries stop and \
+                     every oil product stops with them",
+                    flow.name, flow.surplus
+                );
+                let fix = match flow.name.as_str() {
+                    HEAVY_OIL => Some((plan.heavy_oil_crackers, heavy_cracking)),
+                    LIGHT_OIL => Some((plan.light_oil_crackers, light_cracking)),
+                    _ => None,
+                };
+                if let Some((needed, cracking)) = fix {
+                    issue.push_str(&format!(
+                        "; run {needed} {} plant(s) (have {})",
+                        cracking.recipe.name, cracking.machines
+                    ));
+                }
+                issues.push(issue);
+            }
+            "short" => {
+                let demand = flow.consumed + flow.shortfall;
+                issues.push(format!(
+                    "{} is short by {:.1}/s — its consumers run at {:.0}%",
+                    flow.name,
+                    flow.shortfall,
+                    flow.consumed / demand * 100.0
+                ));
+            }
+            _ => {}
+        }
+    }
+    issues
+}
+
+impl SolveOilBalance {
+    async fn recipe(&self, name: &str) -> Result<RecipeInfo, SenseiError> {
+        GetRecipe::new(self.rcon.clone())
+            .call(GetRecipeArgs {
+                recipe_name: name.to_string(),
+            })
+            .await
+    }
+}
+
+impl Tool for SolveOilBalance {
+    const NAME: &'static str = "solve_oil_balance";
+    type Error = SenseiError;
+    type Args = SolveOilBalanceArgs;
+    type Output = OilBalance;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "solve_oil_balance".to_string(),
+            description: "Calculate the steady-state oil balance for a planned or existing setup: given refinery counts per oil recipe, heavy and light oil cracker counts, and the recipes consuming oil products, returns heavy oil, light oil, and petroleum gas flows per second, which product backs up or runs short, the crude/water/coal/steam needed, and the number of crackers that leaves nothing backed up. Uses the game's recipe data; does not read the map.".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "advanced_refineries": {
+                        "type": "integer",
+                        "description": "Refineries running advanced oil processing (default: 0)"
+                    },
+                    "basic_refineries": {
+                        "type": "integer",
+                        "description": "Refineries running basic oil processing (default: 0)"
+                    },
+                    "coal_liquefaction": {
+                        "type": "integer",
+                        "description": "Refineries running coal liquefaction (default: 0)"
+                    },
+                    "heavy_oil_crackers": {
+                        "type": "integer",
+                        "description": "Chemical plants running heavy oil cracking (default: 0)"
+                    },
+                    "light_oil_crackers": {
+                        "type": "integer",
+                        "description": "Chemical plants running light oil cracking (default: 0)"
+                    },
+                    "consumers": {
+                        "type": "array",
+                        "description": "Recipes consuming oil products, e.g. [{\"recipe\": \"plastic-bar\", \"machines\": 8}]",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "recipe": {
+                                    "type": "string",
+                                    "description": "Recipe name (e.g. 'plastic-bar', 'lubricant', 'sulfur', 'solid-fuel-from-light-oil')"
+                                },
+                                "machines": {
+                                    "type": "integer",
+                                    "description": "Machines running the recipe"
+                                },
+                                "crafting_speed": {
+                                    "type": "number",
+                                    "description": "Crafting speed of those machines (default: chemical_plant_speed)"
+                                }
+                            },
+                            "required": ["recipe", "machines"]
+                        }
+                    },
+                    "refinery_speed": {
+                        "type": "number",
+                        "description": "Crafting speed of the refineries (default: 1.0)"
+                    },
+                    "chemical_plant_speed": {
+                        "type": "number",
+                        "description": "Crafting speed of the chemical plants (default: 1.0)"
+                    }
+                }
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let refinery_speed = args.refinery_speed.unwrap_or(1.0);
+        let chemical_plant_speed = args.chemical_plant_speed.unwrap_or(1.0);
+
+        let mut refining_recipes = Vec::new();
+        for (name, count) in [
+            (ADVANCED_OIL_PROCESSING, args.advanced_refineries),
+            (BASIC_OIL_PROCESSING, args.basic_refineries),
+            (COAL_LIQUEFACTION, args.coal_liquefaction),
+        ] {
+            if let Some(count) = count.filter(|c| *c > 0) {
+                refining_recipes.push((self.recipe(name).await?, count));
+            }
+        }
+        let heavy_recipe = self.recipe(HEAVY_OIL_CRACKING).await?;
+        let light_recipe = self.recipe(LIGHT_OIL_CRACKING).await?;
+        let consumers = args.consumers.unwrap_or_default();
+        let mut consumer_recipes = Vec::with_capacity(consumers.len());
+        for consumer in &consumers {
+            consumer_recipes.push(self.recipe(&consumer.recipe).await?);
+        }
+
+        let refining: Vec<RecipeRun> = refining_recipes
+            .iter()
+            .map(|(recipe, count)| RecipeRun {
+                recipe,
+                machines: f64::from(*count),
+                crafting_speed: refinery_speed,
+            })
+            .collect();
+        let heavy_cracking = RecipeRun {
+            recipe: &heavy_recipe,
+            machines: f64::from(args.heavy_oil_crackers.unwrap_or(0)),
+            crafting_speed: chemical_plant_speed,
+        };
+        let light_cracking = RecipeRun {
+            recipe: &light_recipe,
+            machines: f64::from(args.light_oil_crackers.unwrap_or(0)),
+            crafting_speed: chemical_plant_speed,
+        };
+        let consuming: Vec<RecipeRun> = consumers
+            .iter()
+            .zip(&consumer_recipes)
+            .map(|(consumer, recipe)| RecipeRun {
+                recipe,
+                machines: f64::from(consumer.machines),
+                crafting_speed: consumer.crafting_speed.unwrap_or(chemical_plant_speed),
+            })
+            .collect();
+
+        Ok(solve(
+            &refining,
+            &heavy_cracking,
+            &light_cracking,
+            &consuming,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Vanilla Factorio 2.0 recipe, as `GetRecipe` returns it.
+    fn recipe(json: &str) -> RecipeInfo {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn advanced() -> RecipeInfo {
+        recipe(
+            r#"{"name":"advanced-oil-processing","energy":5,
+                "ingredients":[{"name":"water","type":"fluid","amount":50},{"name":"crude-oil","type":"fluid","amount":100}],
+                "products":[{"name":"heavy-oil","type":"fluid","amount":25},{"name":"light-oil","type":"fluid","amount":45},
+                            {"name":"petroleum-gas","type":"fluid","amount":55}]}"#,
+        )
+    }
+
+    fn coal_liquefaction() -> RecipeInfo {
+        recipe(
+            r#"{"name":"coal-liquefaction","energy":5,
+                "ingredients":[{"name":"coal","type":"item","amount":10},{"name":"heavy-oil","type":"fluid","amount":25},
+                               {"name":"steam","type":"fluid","amount":50}],
+                "products":[{"name":"heavy-oil","type":"fluid","amount":90},{"name":"light-oil","type":"fluid","amount":20},
+                            {"name":"petroleum-gas","type":"fluid","amount":10}]}"#,
+        )
+    }
+
+    fn heavy_cracking() -> RecipeInfo {
+        recipe(
+            r#"{"name":"heavy-oil-cracking","energy":2,
+                "ingredients":[{"name":"water","type":"fluid","amount":30},{"name":"heavy-oil","type":"fluid","amount":40}],
+                "products":[{"name":"light-oil","type":"fluid","amount":30}]}"#,
+        )
+    }
+
+    fn light_cracking() -> RecipeInfo {
+        recipe(
+            r#"{"name":"light-oil-cracking","energy":2,
+                "ingredients":[{"name":"water","type":"fluid","amount":30},{"name":"light-oil","type":"fluid","amount":30}],
+                "products":[{"name":"petroleum-gas","type":"fluid","amount":20}]}"#,
+        )
+    }
+
+    fn plastic() -> RecipeInfo {
+        recipe(
+            r#"{"name":"plastic-bar","energy":1,
+                "ingredients":[{"name":"petroleum-gas","type":"fluid","amount":20},{"name":"coal","type":"item","amount":1}],
+                "products":[{"name":"plastic-bar","type":"item","amount":2}]}"#,
+        )
+    }
+
+    fn lubricant() -> RecipeInfo {
+        recipe(
+            r#"{"name":"lubricant","energy":1,
+                "ingredients":[{"name":"heavy-oil","type":"fluid","amount":10}],
+                "products":[{"name":"lubricant","type":"fluid","amount":10}]}"#,
+        )
+    }
+
+    fn run(recipe: &RecipeInfo, machines: u32) -> RecipeRun<'_> {
+        RecipeRun {
+            recipe,
+            machines: f64::from(machines),
+            crafting_speed: 1.0,
+        }
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_uncracked_refinery_backs_up_heavy_and_light() {
+        let (adv, hc, lc, plastic) = (advanced(), heavy_cracking(), light_cracking(), plastic());
+        let balance = solve(
+            &[run(&adv, 1)],
+            &run(&hc, 0),
+            &run(&lc, 0),
+            &[run(&plastic, 1)],
+        );
+
+        let [heavy, light, gas] = &balance.fluids[..] else {
+            panic!("expected three fluids");
+        };
+        assert_eq!(heavy.status, "backs_up");
+        assert!(close(heavy.surplus, 5.0));
+        assert_eq!(light.status, "backs_up");
+        assert!(close(light.surplus, 9.0));
+        assert_eq!(gas.status, "short");
+        assert!(close(gas.produced, 11.0));
+        assert!(close(gas.shortfall, 9.0));
+
+        assert!(close(balance.inputs["crude-oil"], 20.0));
+        assert!(close(balance.inputs["water"], 10.0));
+        // Consumer inputs (the plastic's coal) are not part of the oil setup
+        assert!(!balance.inputs.contains_key("coal"));
+
+        // 5 heavy/s → 3.75 light/s; 12.75 light/s → 8.5 petroleum/s
+        assert_eq!(balance.recommended.heavy_oil_crackers, 1);
+        assert_eq!(balance.recommended.light_oil_crackers, 1);
+        assert!(close(balance.recommended.petroleum_gas_per_second, 19.5));
+        assert!(close(balance.recommended.shortfall["petroleum-gas"], 0.5));
+
+        assert_eq!(balance.issues.len(), 3);
+        assert!(balance.issues[0].starts_with("heavy-oil backs up by 5.0/s"));
+        assert!(balance.issues[0].ends_with("run 1 heavy-oil-cracking plant(s) (have 0)"));
+        assert_eq!(
+            balance.issues[2],
+            "petroleum-gas is short by 9.0/s — its consumers run at 55%"
+        );
+    }
+
+    #[test]
+    fn test_consumers_take_heavy_oil_before_crackers() {
+        let (adv, hc, lc, lube) = (advanced(), heavy_cracking(), light_cracking(), lubricant());
+        // 10 refineries: 50 heavy/s, lubricant takes 10, crackers have room for 20
+        let balance = solve(
+            &[run(&adv, 10)],
+            &run(&hc, 1),
+            &run(&lc, 10),
+            &[run(&lube, 1)],
+        );
+
+        let heavy = &balance.fluids[0];
+        assert!(close(heavy.consumed, 30.0));
+        assert!(close(heavy.surplus, 20.0));
+        assert_eq!(balance.recommended.heavy_oil_crackers, 2);
+        // Only the heavy oil actually cracked draws water
+        assert!(close(balance.inputs["water"], 100.0 + 15.0 + 105.0));
+        assert!(balance.issues[0].ends_with("(have 1)"));
+        assert!(balance.recommended.shortfall.is_empty());
+    }
+
+    #[test]
+    fn test_fully_cracked_setup_only_backs_up_unused_petroleum() {
+        let (adv, hc, lc) = (advanced(), heavy_cracking(), light_cracking());
+        // 20 refineries: 100 heavy/s → 5 crackers; 180 + 75 light/s → 17 crackers (255/15)
+        let balance = solve(&[run(&adv, 20)], &run(&hc, 5), &run(&lc, 17), &[]);
+        assert_eq!(balance.fluids[0].status, "balanced");
+        assert_eq!(balance.fluids[1].status, "balanced");
+        assert_eq!(balance.recommended.heavy_oil_crackers, 5);
+        assert_eq!(balance.recommended.light_oil_crackers, 17);
+        // No consumer: petroleum has nowhere to go
+        assert_eq!(balance.fluids[2].status, "backs_up");
+        assert!(close(balance.fluids[2].surplus, 220.0 + 170.0));
+        assert_eq!(balance.issues.len(), 1);
+        assert!(balance.issues[0].starts_with("petroleum-gas backs up"));
+    }
+
+    #[test]
+    fn test_coal_liquefaction_feeds_its_own_heavy_oil() {
+        let (coal, hc, lc) = (coal_liquefaction(), heavy_cracking(), light_cracking());
+        let balance = solve(&[run(&coal, 1)], &run(&hc, 0), &run(&lc, 0), &[]);
+        let heavy = &balance.fluids[0];
+        assert!(close(heavy.produced, 18.0));
+        assert!(close(heavy.surplus, 13.0));
+        assert!(close(balance.inputs["coal"], 2.0));
+        assert!(close(balance.inputs["steam"], 10.0));
+        assert!(!balance.inputs.contains_key("heavy-oil"));
+    }
+}
@@ -0,0 +1,229 @@
+//! Tool for a factory-wide overview of what is produced, consumed, and short.
+//!
+//! Walks every item in the force's item production statistics for one surface
+//! (`input_counts` and `output_counts`), reads the per-minute flow over one
+//! precision window, and ranks the results on the Rust side into top producers,
+//! top consumers, and the largest net deficits. Answers "what am I short on?" in one call.
+
+use rig::{completion::ToolDefinition, tool::Tool};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::{production::FlowWindow, surface_param};
+use crate::{
+    error::SenseiError,
+    lua,
+    rcon_ext::{execute_lua_json, SharedRcon},
+};
+
+/// Ranks every produced/consumed item by its per-minute flow over a window.
+pub struct GetProductionOverview {
+    pub(crate) rcon: SharedRcon,
+}
+
+impl GetProductionOverview {
+    pub const fn new(rcon: SharedRcon) -> Self {
+        Self { rcon }
+    }
+}
+
+/// Arguments for [`GetProductionOverview`]. All optional.
+#[derive(Debug, Deserialize)]
+pub struct GetProductionOverviewArgs {
+    /// Time window for the rates. Defaults to 10 minutes.
+    pub window: Option<FlowWindow>,
+    /// Number of entries in each ranking. Defaults to 10.
+    pub top: Option<usize>,
+    #[doc = surface_arg_doc!()]
+    pub surface: Option<String>,
+}
+
+/// Raw per-name flow as returned by the Lua side.
+#[derive(Debug, Clone, Deserialize)]
+pub(super) struct FlowRow {
+    pub(super) name: String,
+    /// All-time produced total, only sent for the row that asked for totals.
+    pub(super) produced: Option<f64>,
+    /// All-time consumed total, only sent for the row that asked for totals.
+    pub(super) consumed: Option<f64>,
+    pub(super) produced_per_min: f64,
+    pub(super) consumed_per_min: f64,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct FlowRows {
+    #[serde(default)]
+    pub(super) items: Vec<FlowRow>,
+}
+
+/// Per-minute flow of one item (or fluid) over the selected window.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FlowRate {
+    /// Prototype name (e.g. "iron-plate").
+    pub name: String,
+    /// Average units produced per minute.
+    pub produced_per_min: f64,
+    /// Average units consumed per minute.
+    pub consumed_per_min: f64,
+    /// `produced_per_min - consumed_per_min`; negative means a deficit.
+    pub net_per_min: f64,
+}
+
+impl From<FlowRow> for FlowRate {
+    fn from(row: FlowRow) -> Self {
+        Self {
+            net_per_min: row.produced_per_min - row.consumed_per_min,
+            name: row.name,
+            produced_per_min: row.produced_per_min,
+            consumed_per_min: row.consumed_per_min,
+        }
+    }
+}
+
+/// Factory-wide rankings over one window.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ProductionOverview {
+    /// The window the rates were averaged over.
+    pub window: FlowWindow,
+    /// Highest production rates first.
+    pub top_produced: Vec<FlowRate>,
+    /// Highest consumption rates first.
+    pub top_consumed: Vec<FlowRate>,
+    /// Items consumed faster than produced, largest shortfall first.
+    pub deficits: Vec<FlowRate>,
+}
+
+impl ProductionOverview {
+    /// Rank `rates` into the top-`top` producers, consumers, and deficits.
+    pub(crate) fn rank(window: FlowWindow, rates: &[FlowRate], top: usize) -> Self {
+        let ranked = |key: fn(&FlowRate) -> f64| {
+            let mut sorted: Vec<FlowRate> =
+                rates.iter().filter(|r| key(r) > 0.0).cloned().collect();
+            sorted.sort_by(|a, b| key(b).total_cmp(&key(a)));
+            sorted.truncate(top);
+            sorted
+        };
+        Self {
+            window,
+            top_produced: ranked(|r| r.produced_per_min),
+            top_consumed: ranked(|r| r.consumed_per_min),
+            deficits: ranked(|r| -r.net_per_min),
+        }
+    }
+}
+
+impl Tool for GetProductionOverview {
+    const NAME: &'static str = "get_production_overview";
+    type Error = SenseiError;
+    type Args = GetProductionOverviewArgs;
+    type Output = ProductionOverview;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "get_production_overview".to_string(),
+            description: "Get a factory-wide item overview: top produced, top consumed, and largest net deficits (per minute) over a time window. Use this to find what the factory is short on.".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "window": {
+                        "type": "string",
+                        "enum": FlowWindow::NAMES,
+                        "description": "Time window for the per-minute rates (default: '10m')"
+                    },
+                    "top": {
+                        "type": "integer",
+                        "description": "Number of entries in each ranking (default: 10)"
+                    },
+                    "surface": surface_param()
+                }
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let window = args.window.unwrap_or_default();
+        let lua = lua::production_overview(
+            window.precision_index(),
+            window.minutes(),
+            args.surface.as_deref(),
+        );
+        let json = execute_lua_json(&self.rcon, &lua).await?;
+        let rows: FlowRows = serde_json::from_str(&json)?;
+        let rates: Vec<FlowRate> = rows.items.into_iter().map(FlowRate::from).collect();
+        Ok(ProductionOverview::rank(
+            window,
+            &rates,
+            args.top.unwrap_or(10),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate(name: &str, produced: f64, consumed: f64) -> FlowRate {
+        FlowRate::from(FlowRow {
+            name: name.to_string(),
+            produced: None,
+            consumed: None,
+            produced_per_min: produced,
+            consumed_per_min: consumed,
+        })
+    }
+
+    #[test]
+    fn test_parse_flow_rows() {
+        let json = r#"{"items":[
+            {"name":"iron-plate","produced_per_min":40,"consumed_per_min":55},
+            {"name":"copper-plate","produced_per_min":30.5,"consumed_per_min":0}
+        ]}"#;
+        let rows: FlowRows = serde_json::from_str(json).unwrap();
+        assert_eq!(rows.items.len(), 2);
+        let iron = FlowRate::from(rows.items.into_iter().next().unwrap());
+        assert_eq!(iron.net_per_min, -15.0);
+    }
+
+    #[test]
+    fn test_rank_orders_and_truncates() {
+        let rates = vec![
+            rate("iron-plate", 40.0, 55.0),
+            rate("copper-plate", 90.0, 30.0),
+            rate("iron-gear-wheel", 10.0, 12.0),
+            rate("coal", 5.0, 0.0),
+        ];
+        let overview = ProductionOverview::rank(FlowWindow::TenMinutes, &rates, 2);
+        let names = |v: &[FlowRate]| v.iter().map(|r| r.name.clone()).collect::<Vec<_>>();
+        assert_eq!(
+            names(&overview.top_produced),
+            ["copper-plate", "iron-plate"]
+        );
+        assert_eq!(
+            names(&overview.top_consumed),
+            ["iron-plate", "copper-plate"]
+        );
+        assert_eq!(names(&overview.deficits), ["iron-plate", "iron-gear-wheel"]);
+    }
+
+    #[test]
+    fn test_parse_no_rows() {
+        let rows: FlowRows = serde_json::from_str("{}").unwrap();
+        assert!(rows.items.is_empty());
+    }
+
+    #[test]
+    fn test_rank_no_deficits() {
+        let rates = vec![rate("iron-plate", 40.0, 40.0), rate("coal", 5.0, 1.0)];
+        let overview = ProductionOverview::rank(FlowWindow::OneHour, &rates, 10);
+        assert!(overview.deficits.is_empty());
+        assert_eq!(overview.top_produced.len(), 2);
+    }
+
+    #[test]
+    fn test_rank_empty() {
+        let overview = ProductionOverview::rank(FlowWindow::OneMinute, &[], 10);
+        assert!(overview.top_produced.is_empty());
+        assert!(overview.top_consumed.is_empty());
+        assert!(overview.deficits.is_empty());
+    }
+}
@@ -0,0 +1,267 @@
+//! Tool for reading pollution and enemy evolution.
+//!
+//! Reports the enemy evolution factor on the surface split into its time,
+//! pollution, and spawner-kill components, the map's evolution factors, which
+//! entities emit pollution, and pollution in the chunks around the player.
+//!
+//! The next evolution threshold comes from the knowledge base table, and the time
+//! to reach it is projected on the Rust side from the evolution formula using the
+//! current pollution rate (spawner kills are ignored as they are not steady).
+
+use std::collections::BTreeMap;
+
+use rig::{completion::ToolDefinition, tool::Tool};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::surface_param;
+use crate::{
+    error::SenseiError,
+    lua,
+    rcon_ext::{execute_lua_json, SharedRcon},
+};
+
+/// Evolution factors at which new enemy types start appearing.
+const EVOLUTION_THRESHOLDS: &[(f64, &str)] = &[
+    (0.2, "medium biters"),
+    (0.25, "small spitters"),
+    (0.3, "medium worms"),
+    (0.4, "medium spitters"),
+    (0.5, "big biters, big spitters, big worms"),
+    (0.9, "behemoth biters, spitters, and worms"),
+];
+
+/// Reads evolution, pollution sources, and chunk pollution around the player.
+pub struct GetPollution {
+    pub(crate) rcon: SharedRcon,
+}
+
+impl GetPollution {
+    pub const fn new(rcon: SharedRcon) -> Self {
+        Self { rcon }
+    }
+}
+
+/// Arguments for [`GetPollution`]. All optional.
+#[derive(Debug, Deserialize)]
+pub struct GetPollutionArgs {
+    /// Chunks (32×32 tiles) to scan in each direction from the player. Defaults to 8, max 32.
+    pub chunk_radius: Option<u32>,
+    #[doc = surface_arg_doc!()]
+    pub surface: Option<String>,
+}
+
+/// The enemy evolution factor and its components.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct Evolution {
+    /// Current evolution factor, 0.0–1.0.
+    pub factor: f64,
+    pub by_time: f64,
+    pub by_pollution: f64,
+    pub by_killing_spawners: f64,
+}
+
+/// Map settings driving evolution.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct EvolutionSettings {
+    pub enabled: bool,
+    /// Evolution per second of game time.
+    pub time_factor: f64,
+    /// Evolution per unit of pollution produced.
+    pub pollution_factor: f64,
+    /// Evolution per spawner destroyed.
+    pub destroy_factor: f64,
+}
+
+/// One polluting entity prototype.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct PollutionSource {
+    /// Entity prototype name (e.g. "boiler", "electric-mining-drill").
+    pub name: String,
+    /// Entity type (e.g. "boiler", "mining-drill").
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Pollution emitted per minute (10-minute average).
+    pub per_minute: f64,
+}
+
+/// A polluted chunk, by its center position.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ChunkHotspot {
+    pub x: f64,
+    pub y: f64,
+    pub pollution: f64,
+}
+
+/// Pollution in the chunks around the player.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ChunkPollution {
+    /// Generated chunks scanned.
+    pub scanned: u32,
+    /// Chunks holding any pollution.
+    pub polluted: u32,
+    /// Total pollution across scanned chunks.
+    pub total: f64,
+    /// Up to 10 most polluted chunks.
+    #[serde(default)]
+    pub hotspots: Vec<ChunkHotspot>,
+}
+
+/// The next evolution threshold and a projection of when it is reached.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct EvolutionThreshold {
+    pub evolution: f64,
+    /// Enemies that start appearing there.
+    pub unlocks: String,
+    /// Projected minutes at the current time and pollution rate, or `None` when
+    /// evolution is disabled.
+    pub minutes: Option<f64>,
+}
+
+/// Top-level pollution and evolution report.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct Pollution {
+    pub evolution: Evolution,
+    pub settings: EvolutionSettings,
+    /// Polluting entities, largest emitters first.
+    #[serde(default)]
+    pub sources: Vec<PollutionSource>,
+    /// Emission per minute summed per entity type.
+    #[serde(default)]
+    pub by_type: BTreeMap<String, f64>,
+    /// Total emission per minute on the surface.
+    #[serde(default)]
+    pub production_per_min: f64,
+    pub chunks: ChunkPollution,
+    /// `None` once evolution is past the last threshold.
+    #[serde(default)]
+    pub next_threshold: Option<EvolutionThreshold>,
+}
+
+impl Pollution {
+    /// Sort sources, total them per type, and project the next evolution threshold.
+    fn analyze(&mut self) {
+        self.sources
+            .sort_by(|a, b| b.per_minute.total_cmp(&a.per_minute));
+        self.production_per_min = self.sources.iter().map(|s| s.per_minute).sum();
+        self.by_type = BTreeMap::new();
+        for source in &self.sources {
+            *self.by_type.entry(source.kind.clone()).or_default() += source.per_minute;
+        }
+        self.next_threshold = EVOLUTION_THRESHOLDS
+            .iter()
+            .find(|(t, _)| *t > self.evolution.factor)
+            .map(|&(evolution, unlocks)| EvolutionThreshold {
+                evolution,
+                unlocks: unlocks.to_string(),
+                minutes: self.minutes_to(evolution),
+            });
+    }
+
+    /// Minutes until evolution reaches `target`, from
+    /// `ln((1 − evolution) / (1 − target)) / k` with
+    /// `k = time_factor + pollution_factor × pollution_per_second`.
+    fn minutes_to(&self, target: f64) -> Option<f64> {
+        let s = &self.settings;
+        let k = s.time_factor + s.pollution_factor * self.production_per_min / 60.0;
+        if !s.enabled || k <= 0.0 || target >= 1.0 {
+            return None;
+        }
+        let seconds = ((1.0 - self.evolution.factor) / (1.0 - target)).ln() / k;
+        Some(seconds / 60.0)
+    }
+}
+
+impl Tool for GetPollution {
+    const NAME: &'static str = "get_pollution";
+    type Error = SenseiError;
+    type Args = GetPollutionArgs;
+    type Output = Pollution;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "get_pollution".to_string(),
+            description: "Get enemy evolution (split into time, pollution, and spawner kills), pollution emitted per minute by entity and entity type, pollution in chunks around the player with the worst hotspots, and the next evolution threshold (e.g. medium biters at 0.2) with a projection of when it is reached.".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "chunk_radius": {
+                        "type": "integer",
+                        "description": "Chunks (32x32 tiles) to scan in each direction around the player (default: 8, max: 32)"
+                    },
+                    "surface": surface_param()
+                }
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let chunk_radius = args.chunk_radius.unwrap_or(8).min(32);
+        let lua = lua::pollution(chunk_radius, args.surface.as_deref());
+        let json = execute_lua_json(&self.rcon, &lua).await?;
+        let mut result: Pollution = serde_json::from_str(&json)?;
+        result.analyze();
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pollution(factor: f64, per_minute: f64) -> Pollution {
+        let json = format!(
+            r#"{{"evolution":{{"factor":{factor},"by_time":0,"by_pollution":0,"by_killing_spawners":0}},
+                "settings":{{"enabled":true,"time_factor":0.000004,"pollution_factor":0.0000009,"destroy_factor":0.002}},
+                "sources":[{{"name":"boiler","type":"boiler","per_minute":{per_minute}}}],
+                "chunks":{{"scanned":289,"polluted":0,"total":0}}}}"#
+        );
+        let mut p: Pollution = serde_json::from_str(&json).unwrap();
+        p.analyze();
+        p
+    }
+
+    #[test]
+    fn test_parse_pollution() {
+        let json = r#"{"evolution":{"factor":0.31,"by_time":0.1,"by_pollution":0.18,"by_killing_spawners":0.03},
+            "settings":{"enabled":true,"time_factor":0.000004,"pollution_factor":0.0000009,"destroy_factor":0.002},
+            "sources":[{"name":"burner-mining-drill","type":"mining-drill","per_minute":120},
+                       {"name":"boiler","type":"boiler","per_minute":300},
+                       {"name":"electric-mining-drill","type":"mining-drill","per_minute":100}],
+            "chunks":{"scanned":289,"polluted":40,"total":52000,"hotspots":[{"x":16,"y":-48,"pollution":3100}]}}"#;
+        let mut p: Pollution = serde_json::from_str(json).unwrap();
+        p.analyze();
+        assert_eq!(p.sources[0].name, "boiler");
+        assert!((p.production_per_min - 520.0).abs() < 1e-9);
+        assert!((p.by_type["mining-drill"] - 220.0).abs() < 1e-9);
+        assert_eq!(p.chunks.hotspots[0].pollution, 3100.0);
+        let next = p.next_threshold.unwrap();
+        assert_eq!(next.evolution, 0.4);
+        assert_eq!(next.unlocks, "medium spitters");
+    }
+
+    #[test]
+    fn test_threshold_projection() {
+        // k = 0.000004 + 0.0000009 × 6000/60 = 0.000094 per second;
+        // from 0.1 to 0.2: ln(0.9 / 0.8) / k ≈ 1253 s ≈ 20.9 minutes
+        let p = pollution(0.1, 6000.0);
+        let next = p.next_threshold.unwrap();
+        assert_eq!(next.unlocks, "medium biters");
+        let minutes = next.minutes.unwrap();
+        assert!((minutes - (0.9f64 / 0.8).ln() / 0.000_094 / 60.0).abs() < 1e-9);
+        assert!(minutes > 20.0 && minutes < 21.0);
+    }
+
+    #[test]
+    fn test_no_threshold_past_behemoths() {
+        assert_eq!(pollution(0.95, 100.0).next_threshold, None);
+    }
+
+    #[test]
+    fn test_disabled_evolution_has_no_projection() {
+        let mut p = pollution(0.1, 100.0);
+        p.settings.enabled = false;
+        p.analyze();
+        assert_eq!(p.next_threshold.unwrap().minutes, None);
+    }
+}
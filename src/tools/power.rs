@@ -1,20 +1,23 @@
-//! Tool for reading the electric network's production, consumption, and satisfaction.
+//! Tool for reading each electric network's production, consumption, and satisfaction.
 //!
-//! Finds the first electric pole on the player's surface and reads its
-//! `electric_network_statistics`. Returns zero values if no poles exist yet.
-//! Satisfaction < 1.0 means the factory is experiencing brownouts.
+//! Finds every electric pole on the selected surface, groups them by
+//! `electric_network_id`, and reads each network's `electric_network_statistics`
+//! once. Per network it reports the generator breakdown by prototype (steam
+//! engines, solar panels, turbines, discharging accumulators) and accumulator
+//! charge. Satisfaction < 1.0 means that network is experiencing brownouts.
 
 use rig::{completion::ToolDefinition, tool::Tool};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
+use super::surface_param;
 use crate::{
     error::SenseiError,
     lua,
     rcon_ext::{execute_lua_json, SharedRcon},
 };
 
-/// Reads the power grid via the first electric pole's network statistics.
+/// Reads the power statistics of every distinct electric network on a surface.
 pub struct GetPowerStats {
     pub(crate) rcon: SharedRcon,
 }
@@ -25,19 +28,87 @@ impl GetPowerStats {
     }
 }
 
-/// Arguments for [`GetPowerStats`]. Takes no parameters.
+/// Arguments for [`GetPowerStats`]. All optional.
 #[derive(Debug, Deserialize)]
-pub struct GetPowerStatsArgs {}
+pub struct GetPowerStatsArgs {
+    #[doc = surface_arg_doc!()]
+    pub surface: Option<String>,
+}
 
-/// Electric network summary for the player's surface.
+/// Power output of one generator prototype within a network.
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct PowerStats {
-    /// Total power being generated across the network.
+pub struct GeneratorOutput {
+    /// Entity prototype name (e.g. "steam-engine", "solar-panel", "steam-turbine").
+    pub name: String,
+    /// Current output in watts (5-second average).
+    pub watts: f64,
+}
+
+/// Statistics for a single electric network.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ElectricNetwork {
+    /// Factorio's `electric_network_id`.
+    pub id: u64,
+    /// Number of electric poles in this network.
+    pub poles: u32,
+    /// Total power being generated in this network.
     pub production_watts: f64,
-    /// Total power being consumed across the network.
+    /// Total power being consumed in this network.
     pub consumption_watts: f64,
     /// Ratio of production to consumption (1.0 = fully satisfied, <1.0 = brownout).
     pub satisfaction: f64,
+    /// Output per generator prototype, largest first.
+    #[serde(default)]
+    pub generators: Vec<GeneratorOutput>,
+    /// Number of accumulators connected to this network.
+    pub accumulators: u32,
+    /// Stored energy as a fraction of total accumulator capacity, or `None` without accumulators.
+    pub accumulator_charge: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Networks {
+    #[serde(default)]
+    networks: Vec<ElectricNetwork>,
+}
+
+/// Electric network summary for one surface.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct PowerStats {
+    /// Total power being generated across all networks.
+    pub production_watts: f64,
+    /// Total power being consumed across all networks.
+    pub consumption_watts: f64,
+    /// Ratio of total production to consumption (1.0 = fully satisfied, <1.0 = brownout).
+    /// A small isolated network can brown out while this stays at 1.0 — check `networks`.
+    pub satisfaction: f64,
+    /// Each distinct network, largest consumer first.
+    pub networks: Vec<ElectricNetwork>,
+}
+
+impl PowerStats {
+    /// Combine per-network statistics into surface totals.
+    fn from_networks(mut networks: Vec<ElectricNetwork>) -> Self {
+        for network in &mut networks {
+            network
+                .generators
+                .sort_by(|a, b| b.watts.total_cmp(&a.watts));
+        }
+        networks.sort_by(|a, b| b.consumption_watts.total_cmp(&a.consumption_watts));
+        let production_watts: f64 = networks.iter().map(|n| n.production_watts).sum();
+        let consumption_watts: f64 = networks.iter().map(|n| n.consumption_watts).sum();
+        let satisfaction = if consumption_watts > 0.0 {
+            (production_watts / consumption_watts).min(1.0)
+        } else {
+            1.0
+        };
+        Self {
+            production_watts,
+            consumption_watts,
+            satisfaction,
+            networks,
+        }
+    }
 }
 
 impl Tool for GetPowerStats {
@@ -49,18 +120,21 @@ impl Tool for GetPowerStats {
     async fn definition(&self, _prompt: String) -> ToolDefinition {
         ToolDefinition {
             name: "get_power_stats".to_string(),
-            description: "Get the power grid statistics: total production, consumption, and satisfaction ratio".to_string(),
+            description: "Get power statistics for every separate electric network: production, consumption, satisfaction ratio, output per generator type (steam engine, solar, turbine, accumulator), and accumulator charge".to_string(),
             parameters: json!({
                 "type": "object",
-                "properties": {}
+                "properties": {
+                    "surface": surface_param()
+                }
             }),
         }
     }
 
-    async fn call(&self, _args: Self::Args) -> Result<Self::Output, Self::Error> {
-        let lua = lua::power_stats();
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let lua = lua::power_stats(args.surface.as_deref());
         let json = execute_lua_json(&self.rcon, &lua).await?;
-        Ok(serde_json::from_str(&json)?)
+        let parsed: Networks = serde_json::from_str(&json)?;
+        Ok(PowerStats::from_networks(parsed.networks))
     }
 }
 
@@ -68,27 +142,55 @@ impl Tool for GetPowerStats {
 mod tests {
     use super::*;
 
+    fn parse(json: &str) -> PowerStats {
+        let parsed: Networks = serde_json::from_str(json).unwrap();
+        PowerStats::from_networks(parsed.networks)
+    }
+
     #[test]
     fn test_parse_power_stats() {
-        let json = r#"{"production_watts":5000000,"consumption_watts":3500000,"satisfaction":1.0}"#;
-        let stats: PowerStats = serde_json::from_str(json).unwrap();
+        let stats = parse(
+            r#"{"networks":[{"id":1,"poles":120,"production_watts":5000000,"consumption_watts":3500000,"satisfaction":1.0,
+                "generators":[{"name":"solar-panel","watts":1000000},{"name":"steam-engine","watts":4000000}],
+                "accumulators":20,"accumulator_charge":0.75}]}"#,
+        );
         assert_eq!(stats.production_watts, 5_000_000.0);
         assert_eq!(stats.consumption_watts, 3_500_000.0);
         assert_eq!(stats.satisfaction, 1.0);
+        let network = &stats.networks[0];
+        assert_eq!(network.generators[0].name, "steam-engine");
+        assert_eq!(network.accumulator_charge, Some(0.75));
     }
 
     #[test]
     fn test_parse_no_power() {
-        let json = r#"{"production_watts":0,"consumption_watts":0,"satisfaction":1.0}"#;
-        let stats: PowerStats = serde_json::from_str(json).unwrap();
+        let stats = parse("{}");
         assert_eq!(stats.production_watts, 0.0);
         assert_eq!(stats.satisfaction, 1.0);
+        assert!(stats.networks.is_empty());
     }
 
     #[test]
     fn test_parse_brownout() {
-        let json = r#"{"production_watts":1000,"consumption_watts":2000,"satisfaction":0.5}"#;
-        let stats: PowerStats = serde_json::from_str(json).unwrap();
+        let stats = parse(
+            r#"{"networks":[{"id":3,"poles":4,"production_watts":1000,"consumption_watts":2000,"satisfaction":0.5,
+                "generators":[{"name":"steam-engine","watts":1000}],"accumulators":0}]}"#,
+        );
         assert_eq!(stats.satisfaction, 0.5);
+        assert_eq!(stats.networks[0].accumulator_charge, None);
+    }
+
+    #[test]
+    fn test_outpost_brownout_visible_per_network() {
+        let stats = parse(
+            r#"{"networks":[
+                {"id":7,"poles":10,"production_watts":100000,"consumption_watts":400000,"satisfaction":0.25,"accumulators":0},
+                {"id":1,"poles":300,"production_watts":9000000,"consumption_watts":5000000,"satisfaction":1.0,"accumulators":0}
+            ]}"#,
+        );
+        // Totals look healthy, but the outpost network is browning out
+        assert_eq!(stats.satisfaction, 1.0);
+        assert_eq!(stats.networks[0].id, 1);
+        assert_eq!(stats.networks[1].satisfaction, 0.25);
     }
 }
@@ -1,20 +1,23 @@
-//! Tool for querying cumulative production/consumption of a specific item.
+//! Tool for querying production/consumption of a specific item.
 //!
-//! Uses `force.get_item_production_statistics("nauvis")` to read the all-time
-//! input (produced) and output (consumed) counts. Sensei can compare these
-//! to spot bottlenecks (e.g. consuming more iron plates than producing).
+//! Uses `force.get_item_production_statistics(surface)` to read the all-time
+//! input (produced) and output (consumed) counts, plus the per-minute rates over
+//! one of Factorio's flow precision windows via `get_flow_count`. Sensei can
+//! compare these to spot bottlenecks (e.g. consuming more iron plates than producing).
+//! Statistics are per surface; the player's current surface is used by default.
 
 use rig::{completion::ToolDefinition, tool::Tool};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
+use super::surface_param;
 use crate::{
     error::SenseiError,
     lua,
     rcon_ext::{execute_lua_json, SharedRcon},
 };
 
-/// Fetches all-time produced/consumed counts for one item on nauvis.
+/// Fetches produced/consumed totals and rates for one item on one surface.
 pub struct GetProductionStats {
     pub(crate) rcon: SharedRcon,
 }
@@ -25,22 +28,88 @@ impl GetProductionStats {
     }
 }
 
+/// One of Factorio's flow statistics precision windows (`defines.flow_precision_index`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FlowWindow {
+    #[serde(rename = "5s")]
+    FiveSeconds,
+    #[serde(rename = "1m")]
+    OneMinute,
+    #[default]
+    #[serde(rename = "10m")]
+    TenMinutes,
+    #[serde(rename = "1h")]
+    OneHour,
+    #[serde(rename = "10h")]
+    TenHours,
+    #[serde(rename = "50h")]
+    FiftyHours,
+    #[serde(rename = "250h")]
+    TwoHundredFiftyHours,
+    #[serde(rename = "1000h")]
+    OneThousandHours,
+}
+
+impl FlowWindow {
+    /// Argument names accepted by tools, in the order shown to the model.
+    pub const NAMES: [&'static str; 8] = ["5s", "1m", "10m", "1h", "10h", "50h", "250h", "1000h"];
+
+    /// Name of the matching `defines.flow_precision_index` entry.
+    pub const fn precision_index(self) -> &'static str {
+        match self {
+            Self::FiveSeconds => "five_seconds",
+            Self::OneMinute => "one_minute",
+            Self::TenMinutes => "ten_minutes",
+            Self::OneHour => "one_hour",
+            Self::TenHours => "ten_hours",
+            Self::FiftyHours => "fifty_hours",
+            Self::TwoHundredFiftyHours => "two_hundred_fifty_hours",
+            Self::OneThousandHours => "one_thousand_hours",
+        }
+    }
+
+    /// Length of the window in minutes, used to turn window counts into per-minute rates.
+    pub const fn minutes(self) -> f64 {
+        match self {
+            Self::FiveSeconds => 5.0 / 60.0,
+            Self::OneMinute => 1.0,
+            Self::TenMinutes => 10.0,
+            Self::OneHour => 60.0,
+            Self::TenHours => 600.0,
+            Self::FiftyHours => 3_000.0,
+            Self::TwoHundredFiftyHours => 15_000.0,
+            Self::OneThousandHours => 60_000.0,
+        }
+    }
+}
+
 /// Arguments for [`GetProductionStats`].
 #[derive(Debug, Deserialize)]
 pub struct GetProductionStatsArgs {
     /// Item prototype name to query (e.g. "iron-plate", "electronic-circuit").
     pub item: String,
+    /// Time window for the per-minute rates. Defaults to 10 minutes.
+    pub window: Option<FlowWindow>,
+    #[doc = surface_arg_doc!()]
+    pub surface: Option<String>,
 }
 
-/// Cumulative production and consumption totals for a single item.
+/// Production and consumption totals and rates for a single item.
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct ProductionStats {
     /// The queried item name, echoed back for context.
     pub item: String,
+    /// The window the rates were averaged over (filled in on the Rust side).
+    #[serde(default)]
+    pub window: FlowWindow,
     /// All-time count of this item produced (input side of statistics).
     pub produced: u64,
     /// All-time count of this item consumed (output side of statistics).
     pub consumed: u64,
+    /// Average items produced per minute over the window.
+    pub produced_per_min: f64,
+    /// Average items consumed per minute over the window.
+    pub consumed_per_min: f64,
 }
 
 impl Tool for GetProductionStats {
@@ -52,7 +121,7 @@ impl Tool for GetProductionStats {
     async fn definition(&self, _prompt: String) -> ToolDefinition {
         ToolDefinition {
             name: "get_production_stats".to_string(),
-            description: "Get total production and consumption statistics for a specific item"
+            description: "Get production and consumption of a specific item: per-minute rates over a time window plus all-time totals"
                 .to_string(),
             parameters: json!({
                 "type": "object",
@@ -60,7 +129,13 @@ impl Tool for GetProductionStats {
                     "item": {
                         "type": "string",
                         "description": "The item prototype name (e.g. 'iron-plate', 'electronic-circuit')"
-                    }
+                    },
+                    "window": {
+                        "type": "string",
+                        "enum": FlowWindow::NAMES,
+                        "description": "Time window for the per-minute rates (default: '10m')"
+                    },
+                    "surface": surface_param()
                 },
                 "required": ["item"]
             }),
@@ -68,9 +143,17 @@ impl Tool for GetProductionStats {
     }
 
     async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
-        let lua = lua::production_stats(&args.item);
+        let window = args.window.unwrap_or_default();
+        let lua = lua::production_stats(
+            &args.item,
+            window.precision_index(),
+            window.minutes(),
+            args.surface.as_deref(),
+        );
         let json = execute_lua_json(&self.rcon, &lua).await?;
-        Ok(serde_json::from_str(&json)?)
+        let mut stats: ProductionStats = serde_json::from_str(&json)?;
+        stats.window = window;
+        Ok(stats)
     }
 }
 
@@ -80,18 +163,38 @@ mod tests {
 
     #[test]
     fn test_parse_production_stats() {
-        let json = r#"{"item":"iron-plate","produced":1500,"consumed":800}"#;
+        let json = r#"{"item":"iron-plate","window":"10m","produced":1500,"consumed":800,"produced_per_min":40,"consumed_per_min":55.5}"#;
         let stats: ProductionStats = serde_json::from_str(json).unwrap();
         assert_eq!(stats.item, "iron-plate");
+        assert_eq!(stats.window, FlowWindow::TenMinutes);
         assert_eq!(stats.produced, 1500);
         assert_eq!(stats.consumed, 800);
+        assert_eq!(stats.produced_per_min, 40.0);
+        assert_eq!(stats.consumed_per_min, 55.5);
     }
 
     #[test]
     fn test_parse_zero_stats() {
-        let json = r#"{"item":"nuclear-fuel","produced":0,"consumed":0}"#;
+        let json = r#"{"item":"nuclear-fuel","window":"1h","produced":0,"consumed":0,"produced_per_min":0,"consumed_per_min":0}"#;
         let stats: ProductionStats = serde_json::from_str(json).unwrap();
         assert_eq!(stats.produced, 0);
         assert_eq!(stats.consumed, 0);
+        assert_eq!(stats.produced_per_min, 0.0);
+    }
+
+    #[test]
+    fn test_window_names_round_trip() {
+        for name in FlowWindow::NAMES {
+            let window: FlowWindow = serde_json::from_value(json!(name)).unwrap();
+            assert_eq!(serde_json::to_value(window).unwrap(), json!(name));
+        }
+    }
+
+    #[test]
+    fn test_window_minutes() {
+        assert_eq!(FlowWindow::OneMinute.minutes(), 1.0);
+        assert_eq!(FlowWindow::OneHour.minutes(), 60.0);
+        assert_eq!(FlowWindow::default(), FlowWindow::TenMinutes);
+        assert_eq!(FlowWindow::FiveSeconds.precision_index(), "five_seconds");
     }
 }
@@ -1,20 +1,22 @@
 //! Tool for discovering ore patches and other resource deposits near the player.
 //!
-//! Finds all `type="resource"` entities within a radius, then aggregates them
-//! by name — summing amounts and averaging positions to produce one entry per
-//! resource type with its total yield and center coordinates.
+//! Finds all `type="resource"` entities within a radius, then clusters the tiles
+//! of each resource into connected patches — so two separate iron fields show up
+//! as two entries, each with its own amount, bounding box, and distance/direction
+//! from the player. Infinite resources (crude oil) report a yield percentage.
 
 use rig::{completion::ToolDefinition, tool::Tool};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
+use super::surface_param;
 use crate::{
     error::SenseiError,
     lua,
     rcon_ext::{execute_lua_json, SharedRcon},
 };
 
-/// Aggregates nearby resource entities by type, returning total amounts and centers.
+/// Clusters nearby resource entities into patches, returning size, amount, and location.
 pub struct GetNearbyResources {
     pub(crate) rcon: SharedRcon,
 }
@@ -28,26 +30,45 @@ impl GetNearbyResources {
 /// Arguments for [`GetNearbyResources`]. All optional.
 #[derive(Debug, Deserialize)]
 pub struct GetNearbyResourcesArgs {
-    /// Search radius in tiles around the player. Defaults to 50.
+    /// Search radius in tiles around the player (or the map origin on another surface).
+    /// Defaults to 50.
     pub radius: Option<f64>,
+    #[doc = surface_arg_doc!()]
+    pub surface: Option<String>,
 }
 
-/// Aggregated info for one resource type (e.g. all iron-ore tiles combined).
+/// One connected patch of a single resource.
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct ResourcePatch {
     /// Resource prototype name (e.g. "iron-ore", "crude-oil").
     pub name: String,
-    /// Sum of all individual tile amounts within the radius.
-    pub total_amount: u64,
-    /// Average x position of all matching resource tiles.
+    /// Number of resource tiles (or oil wells) in the patch.
+    pub tile_count: u32,
+    /// Sum of all tile amounts. `None` for infinite resources like crude oil.
+    #[serde(default)]
+    pub total_amount: Option<u64>,
+    /// Combined yield of all wells in percent, for infinite resources only.
+    #[serde(default)]
+    pub yield_percent: Option<f64>,
+    /// Bounding box of the patch in tile coordinates.
+    pub left: i64,
+    pub top: i64,
+    pub right: i64,
+    pub bottom: i64,
+    /// Average x position of the patch's tiles.
     pub center_x: f64,
-    /// Average y position of all matching resource tiles.
+    /// Average y position of the patch's tiles.
     pub center_y: f64,
+    /// Distance in tiles from the search origin to the patch center.
+    pub distance: f64,
+    /// Compass direction from the search origin (e.g. "north", "southwest").
+    pub direction: String,
 }
 
-/// Top-level response wrapper with one entry per resource type.
+/// Top-level response wrapper with one entry per patch, nearest first.
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct NearbyResources {
+    #[serde(default)]
     pub resources: Vec<ResourcePatch>,
 }
 
@@ -60,14 +81,15 @@ impl Tool for GetNearbyResources {
     async fn definition(&self, _prompt: String) -> ToolDefinition {
         ToolDefinition {
             name: "get_nearby_resources".to_string(),
-            description: "Get resource patches near the player, aggregated by type with total amounts and center positions".to_string(),
+            description: "Get resource patches near the player, clustered into separate connected patches with amount (or oil yield %), tile count, bounding box, and distance/direction, nearest first".to_string(),
             parameters: json!({
                 "type": "object",
                 "properties": {
                     "radius": {
                         "type": "number",
                         "description": "Search radius in tiles (default: 50)"
-                    }
+                    },
+                    "surface": surface_param()
                 }
             }),
         }
@@ -75,7 +97,7 @@ impl Tool for GetNearbyResources {
 
     async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
         let radius = args.radius.unwrap_or(50.0);
-        let lua = lua::nearby_resources(radius);
+        let lua = lua::nearby_resources(radius, args.surface.as_deref());
         let json = execute_lua_json(&self.rcon, &lua).await?;
         Ok(serde_json::from_str(&json)?)
     }
@@ -88,19 +110,38 @@ mod tests {
     #[test]
     fn test_parse_resources() {
         let json = r#"{"resources":[
-            {"name":"iron-ore","total_amount":50000,"center_x":10.5,"center_y":-20.3},
-            {"name":"copper-ore","total_amount":30000,"center_x":50.0,"center_y":15.0}
+            {"name":"iron-ore","tile_count":420,"total_amount":50000,"left":0,"top":-30,
+             "right":21,"bottom":-10,"center_x":10.5,"center_y":-20.3,"distance":22.9,
+             "direction":"northeast"},
+            {"name":"copper-ore","tile_count":300,"total_amount":30000,"left":40,"top":5,
+             "right":60,"bottom":25,"center_x":50.0,"center_y":15.0,"distance":52.2,
+             "direction":"east"}
         ]}"#;
         let result: NearbyResources = serde_json::from_str(json).unwrap();
         assert_eq!(result.resources.len(), 2);
         assert_eq!(result.resources[0].name, "iron-ore");
-        assert_eq!(result.resources[0].total_amount, 50000);
+        assert_eq!(result.resources[0].total_amount, Some(50000));
+        assert_eq!(result.resources[0].direction, "northeast");
+        assert!(result.resources[0].yield_percent.is_none());
     }
 
     #[test]
-    fn test_parse_no_resources() {
-        let json = r#"{"resources":[]}"#;
+    fn test_parse_oil_patch() {
+        let json = r#"{"resources":[
+            {"name":"crude-oil","tile_count":6,"yield_percent":1850.5,"left":-80,"top":12,
+             "right":-61,"bottom":40,"center_x":-70.5,"center_y":26.0,"distance":75.1,
+             "direction":"west"}
+        ]}"#;
         let result: NearbyResources = serde_json::from_str(json).unwrap();
+        let oil = &result.resources[0];
+        assert_eq!(oil.tile_count, 6);
+        assert!(oil.total_amount.is_none());
+        assert!((oil.yield_percent.unwrap() - 1850.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_parse_no_resources() {
+        let result: NearbyResources = serde_json::from_str("{}").unwrap();
         assert!(result.resources.is_empty());
     }
 }
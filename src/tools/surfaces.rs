@@ -0,0 +1,101 @@
+//! Tool for listing every surface (planets and space platforms) in the game.
+//!
+//! Iterates `game.surfaces` and reports each surface's planet or platform name
+//! plus cheap `count_entities_filtered` totals for the player's force, so Sensei
+//! can compare how built-up each planet is before drilling into one with the
+//! `surface` argument of the other tools.
+
+use rig::{completion::ToolDefinition, tool::Tool};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    error::SenseiError,
+    lua,
+    rcon_ext::{execute_lua_json, SharedRcon},
+};
+
+/// Lists all surfaces with basic entity counts.
+pub struct ListSurfaces {
+    pub(crate) rcon: SharedRcon,
+}
+
+impl ListSurfaces {
+    pub const fn new(rcon: SharedRcon) -> Self {
+        Self { rcon }
+    }
+}
+
+/// Arguments for [`ListSurfaces`]. Takes no parameters.
+#[derive(Debug, Deserialize)]
+pub struct ListSurfacesArgs {}
+
+/// One surface and how much the player's force has built on it.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct SurfaceInfo {
+    /// Surface name, usable as the `surface` argument of other tools.
+    pub name: String,
+    /// Planet prototype name (e.g. "vulcanus"), or `None` for non-planet surfaces.
+    pub planet: Option<String>,
+    /// Space platform name, or `None` if this surface is not a platform.
+    pub platform: Option<String>,
+    /// Whether the player is currently on this surface.
+    pub player_here: bool,
+    /// Total entities owned by the player's force.
+    pub entities: u64,
+    /// Assembling machines, furnaces, and rocket silos owned by the player's force.
+    pub crafting_machines: u64,
+    /// Mining drills (including pumpjacks) owned by the player's force.
+    pub mining_drills: u64,
+    /// Entities of the enemy force (biters, spawners, worms, etc.).
+    pub enemies: u64,
+}
+
+/// Top-level response wrapper for the surfaces list.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct Surfaces {
+    pub surfaces: Vec<SurfaceInfo>,
+}
+
+impl Tool for ListSurfaces {
+    const NAME: &'static str = "list_surfaces";
+    type Error = SenseiError;
+    type Args = ListSurfacesArgs;
+    type Output = Surfaces;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "list_surfaces".to_string(),
+            description: "List all surfaces (planets like nauvis, vulcanus, fulgora, gleba, and space platforms) with entity counts for comparing planets".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {}
+            }),
+        }
+    }
+
+    async fn call(&self, _args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let lua = lua::surfaces();
+        let json = execute_lua_json(&self.rcon, &lua).await?;
+        Ok(serde_json::from_str(&json)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_surfaces() {
+        let json = r#"{"surfaces":[
+            {"name":"nauvis","planet":"nauvis","player_here":true,"entities":5400,"crafting_machines":120,"mining_drills":64,"enemies":800},
+            {"name":"platform-1","platform":"Orbital Hub","player_here":false,"entities":90,"crafting_machines":4,"mining_drills":0,"enemies":0}
+        ]}"#;
+        let result: Surfaces = serde_json::from_str(json).unwrap();
+        assert_eq!(result.surfaces.len(), 2);
+        assert_eq!(result.surfaces[0].planet.as_deref(), Some("nauvis"));
+        assert!(result.surfaces[0].player_here);
+        assert_eq!(result.surfaces[1].planet, None);
+        assert_eq!(result.surfaces[1].platform.as_deref(), Some("Orbital Hub"));
+    }
+}
@@ -0,0 +1,366 @@
+//! Tool for looking up technologies and estimating how long the research queue takes.
+//!
+//! Reads a technology's prerequisites, unit count, science pack ingredients, unit
+//! time, and unlocked recipes from `force.technologies` (or the current research
+//! when no name is given).
+//!
+//! The queue estimate is computed on the Rust side from the force's labs and its
+//! science pack supply. Labs finish `lab_speed / unit_time` units per second, and
+//! each pack covers its remaining demand from lab stock first, then from production.
+//! The slower of the two bounds is the ETA, and whichever sets it is reported as
+//! the limiting factor.
+
+use rig::{completion::ToolDefinition, tool::Tool};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    error::SenseiError,
+    lua,
+    rcon_ext::{execute_lua_json, SharedRcon},
+};
+
+/// Returns technology details and an ETA for the current research queue.
+pub struct GetTechnology {
+    pub(crate) rcon: SharedRcon,
+}
+
+impl GetTechnology {
+    pub const fn new(rcon: SharedRcon) -> Self {
+        Self { rcon }
+    }
+}
+
+/// Arguments for [`GetTechnology`]. All optional.
+#[derive(Debug, Deserialize)]
+pub struct GetTechnologyArgs {
+    /// Technology name (e.g. "oil-processing"). Defaults to the current research.
+    pub name: Option<String>,
+}
+
+/// A prerequisite technology and whether it is already researched.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct Prerequisite {
+    pub name: String,
+    pub researched: bool,
+}
+
+/// Science packs consumed per research unit.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ResearchIngredient {
+    /// Science pack name (e.g. "automation-science-pack").
+    pub name: String,
+    /// Packs of this type per unit.
+    pub amount: u32,
+}
+
+/// Static and progress details of one technology.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct TechnologyInfo {
+    /// Technology name (e.g. "advanced-circuit").
+    pub name: String,
+    /// Level for infinite/multi-level techs (1 otherwise).
+    pub level: u32,
+    /// Whether the force has already researched it.
+    pub researched: bool,
+    #[serde(default)]
+    pub prerequisites: Vec<Prerequisite>,
+    /// Number of research units required.
+    pub unit_count: u64,
+    /// Seconds one lab at speed 1 needs per unit.
+    pub unit_time: f64,
+    /// Science packs per unit. Empty for trigger techs.
+    #[serde(default)]
+    pub ingredients: Vec<ResearchIngredient>,
+    /// Recipes the tech unlocks.
+    #[serde(default)]
+    pub unlocks_recipes: Vec<String>,
+    /// Research trigger type (e.g. "craft-item", "mine-entity") for techs unlocked by
+    /// gameplay instead of science packs.
+    #[serde(default)]
+    pub trigger: Option<String>,
+}
+
+/// One entry of the research queue.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct QueuedTech {
+    pub name: String,
+    /// Completion fraction (0.0–1.0): live progress for the tech being researched, saved
+    /// progress for techs started earlier and then left.
+    pub progress: f64,
+    pub unit_count: u64,
+    pub unit_time: f64,
+    #[serde(default)]
+    pub ingredients: Vec<ResearchIngredient>,
+}
+
+impl QueuedTech {
+    fn remaining_units(&self) -> f64 {
+        self.unit_count as f64 * (1.0 - self.progress)
+    }
+}
+
+/// Supply of one science pack used by the queue.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct PackSupply {
+    pub name: String,
+    /// Packs already loaded into labs.
+    pub stock: u64,
+    /// Force-wide production per minute (10-minute average).
+    pub produced_per_min: f64,
+    /// Force-wide consumption per minute (10-minute average).
+    pub consumed_per_min: f64,
+}
+
+/// Demand for one science pack over the whole queue.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct PackDemand {
+    pub name: String,
+    /// Packs still needed to finish the queue.
+    pub needed: f64,
+    /// Packs already loaded into labs.
+    pub stock: u64,
+    pub produced_per_min: f64,
+    /// Minutes of production needed beyond lab stock, or `None` if the pack isn't
+    /// produced and stock falls short.
+    pub minutes: Option<f64>,
+}
+
+/// Time to finish the research queue and what bounds it.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct QueueEstimate {
+    /// Minutes the labs need at full speed, ignoring pack supply.
+    pub lab_minutes: Option<f64>,
+    /// Overall ETA in minutes, or `None` if the queue can't finish (no labs, or a pack
+    /// that isn't produced).
+    pub eta_minutes: Option<f64>,
+    /// `"labs"` or the science pack name that sets the ETA.
+    pub limited_by: Option<String>,
+    pub packs: Vec<PackDemand>,
+}
+
+/// Technology lookup plus research queue state.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct Technology {
+    /// The requested tech, or the current research (absent when idle).
+    #[serde(default)]
+    pub technology: Option<TechnologyInfo>,
+    #[serde(default)]
+    pub queue: Vec<QueuedTech>,
+    /// Labs owned by the force across all surfaces.
+    pub labs: u32,
+    /// Sum of lab research speeds including lab speed research and module effects.
+    pub lab_speed: f64,
+    #[serde(default, skip_serializing)]
+    pub packs: Vec<PackSupply>,
+    /// ETA for the queue; `None` when the queue is empty.
+    #[serde(default)]
+    pub estimate: Option<QueueEstimate>,
+}
+
+impl Technology {
+    /// Estimate the queue's finish time from lab throughput and pack supply.
+    fn estimate_queue(&self) -> Option<QueueEstimate> {
+        if self.queue.is_empty() {
+            return None;
+        }
+        let lab_seconds: f64 = self
+            .queue
+            .iter()
+            .map(|t| t.remaining_units() * t.unit_time)
+            .sum();
+        let lab_minutes = (self.lab_speed > 0.0).then(|| lab_seconds / self.lab_speed / 60.0);
+
+        let packs: Vec<PackDemand> = self
+            .packs
+            .iter()
+            .map(|supply| {
+                let needed: f64 = self
+                    .queue
+                    .iter()
+                    .flat_map(|t| {
+                        t.ingredients
+                            .iter()
+                            .filter(|i| i.name == supply.name)
+                            .map(|i| t.remaining_units() * f64::from(i.amount))
+                    })
+                    .sum();
+                let shortfall = needed - supply.stock as f64;
+                let minutes = if shortfall <= 0.0 {
+                    Some(0.0)
+                } else if supply.produced_per_min > 0.0 {
+                    Some(shortfall / supply.produced_per_min)
+                } else {
+                    None
+                };
+                PackDemand {
+                    name: supply.name.clone(),
+                    needed,
+                    stock: supply.stock,
+                    produced_per_min: supply.produced_per_min,
+                    minutes,
+                }
+            })
+            .collect();
+
+        // Without labs nothing progresses; otherwise the slowest pack (or the labs) wins,
+        // and a pack that can't be supplied at all blocks the queue.
+        let mut eta_minutes = lab_minutes;
+        let mut limited_by = Some("labs".to_string());
+        if let Some(mut eta) = lab_minutes {
+            for pack in &packs {
+                match pack.minutes {
+                    None => {
+                        eta_minutes = None;
+                        limited_by = Some(pack.name.clone());
+                        break;
+                    }
+                    Some(m) if m > eta => {
+                        eta = m;
+                        eta_minutes = Some(m);
+                        limited_by = Some(pack.name.clone());
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        Some(QueueEstimate {
+            lab_minutes,
+            eta_minutes,
+            limited_by,
+            packs,
+        })
+    }
+}
+
+impl Tool for GetTechnology {
+    const NAME: &'static str = "get_technology";
+    type Error = SenseiError;
+    type Args = GetTechnologyArgs;
+    type Output = Technology;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "get_technology".to_string(),
+            description: "Look up a technology: prerequisites (and whether they're researched), unit count, science pack ingredients, time per unit, and unlocked recipes. Also returns the research queue with lab count/speed and an ETA to finish it, naming whether labs or a science pack supply is the limit.".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "name": {
+                        "type": "string",
+                        "description": "Technology name, e.g. 'oil-processing' (default: the current research)"
+                    }
+                }
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let lua = lua::technology(args.name.as_deref());
+        let json = execute_lua_json(&self.rcon, &lua).await?;
+        let mut result: Technology = serde_json::from_str(&json)?;
+        result.estimate = result.estimate_queue();
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn technology(json: &str) -> Technology {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_parse_technology() {
+        let result = technology(
+            r#"{"technology":{"name":"advanced-circuit","level":1,"researched":false,
+                "prerequisites":[{"name":"plastics","researched":true}],
+                "unit_count":200,"unit_time":15,
+                "ingredients":[{"name":"automation-science-pack","amount":1},{"name":"logistic-science-pack","amount":1}],
+                "unlocks_recipes":["advanced-circuit"]},
+               "labs":10,"lab_speed":10.0}"#,
+        );
+        let tech = result.technology.unwrap();
+        assert_eq!(tech.unit_count, 200);
+        assert_eq!(tech.ingredients.len(), 2);
+        assert!(tech.prerequisites[0].researched);
+        assert_eq!(tech.unlocks_recipes, vec!["advanced-circuit"]);
+        assert_eq!(result.estimate, None);
+    }
+
+    #[test]
+    fn test_parse_trigger_tech() {
+        let result = technology(
+            r#"{"technology":{"name":"oil-processing","level":1,"researched":false,"unit_count":0,
+                "unit_time":0,"unlocks_recipes":["pumpjack"],"trigger":"mine-entity"},
+               "labs":0,"lab_speed":0}"#,
+        );
+        assert!(result.queue.is_empty());
+        assert!(result.packs.is_empty());
+        let tech = result.technology.unwrap();
+        assert_eq!(tech.trigger.as_deref(), Some("mine-entity"));
+        assert!(tech.ingredients.is_empty());
+        assert!(tech.prerequisites.is_empty());
+    }
+
+    #[test]
+    fn test_estimate_lab_bound() {
+        // 100 units × 30 s on 10 labs at speed 1 = 300 s = 5 minutes; packs are plentiful
+        let result = technology(
+            r#"{"queue":[{"name":"steel-processing","progress":0,"unit_count":100,"unit_time":30,
+                "ingredients":[{"name":"automation-science-pack","amount":1}]}],
+               "labs":10,"lab_speed":10.0,
+               "packs":[{"name":"automation-science-pack","stock":50,"produced_per_min":100,"consumed_per_min":20}]}"#,
+        );
+        let est = result.estimate_queue().unwrap();
+        assert_eq!(est.lab_minutes, Some(5.0));
+        assert_eq!(est.eta_minutes, Some(5.0));
+        assert_eq!(est.limited_by.as_deref(), Some("labs"));
+    }
+
+    #[test]
+    fn test_estimate_pack_bound() {
+        // Half done: 50 red + 50 green left, 10 green in stock, 4 green/min → 10 minutes
+        let result = technology(
+            r#"{"queue":[{"name":"logistics-2","progress":0.5,"unit_count":100,"unit_time":30,
+                "ingredients":[{"name":"automation-science-pack","amount":1},{"name":"logistic-science-pack","amount":1}]}],
+               "labs":10,"lab_speed":10.0,
+               "packs":[{"name":"automation-science-pack","stock":50,"produced_per_min":30,"consumed_per_min":20},
+                        {"name":"logistic-science-pack","stock":10,"produced_per_min":4,"consumed_per_min":4}]}"#,
+        );
+        let est = result.estimate_queue().unwrap();
+        assert_eq!(est.lab_minutes, Some(2.5));
+        assert_eq!(est.eta_minutes, Some(10.0));
+        assert_eq!(est.limited_by.as_deref(), Some("logistic-science-pack"));
+        assert_eq!(est.packs[0].minutes, Some(0.0));
+    }
+
+    #[test]
+    fn test_estimate_unproduced_pack_blocks_queue() {
+        let result = technology(
+            r#"{"queue":[{"name":"sulfur-processing","progress":0,"unit_count":150,"unit_time":30,
+                "ingredients":[{"name":"chemical-science-pack","amount":1}]}],
+               "labs":4,"lab_speed":4.0,
+               "packs":[{"name":"chemical-science-pack","stock":20,"produced_per_min":0,"consumed_per_min":0}]}"#,
+        );
+        let est = result.estimate_queue().unwrap();
+        assert_eq!(est.eta_minutes, None);
+        assert_eq!(est.limited_by.as_deref(), Some("chemical-science-pack"));
+    }
+
+    #[test]
+    fn test_estimate_without_labs() {
+        let result = technology(
+            r#"{"queue":[{"name":"automation","progress":0,"unit_count":10,"unit_time":10,
+                "ingredients":[{"name":"automation-science-pack","amount":1}]}],
+               "labs":0,"lab_speed":0}"#,
+        );
+        let est = result.estimate_queue().unwrap();
+        assert_eq!(est.lab_minutes, None);
+        assert_eq!(est.eta_minutes, None);
+        assert_eq!(est.limited_by.as_deref(), Some("labs"));
+    }
+}
@@ -0,0 +1,255 @@
+//! Tool for inspecting train stops and measuring station throughput.
+//!
+//! Lists the force's train stops by name with their trains limit, the number of
+//! trains en route (`trains_count`), the train currently stopped there, and the
+//! circuit enable/disable condition.
+//!
+//! Deliveries per hour are measured rather than guessed: the tool polls which
+//! train occupies each stop every few seconds for `sample_seconds`, counts new
+//! arrivals, and scales them by the game ticks that passed (so a paused game
+//! yields no rate instead of a wrong one). Sampling waits in real time, so it is
+//! opt-in and capped at two minutes.
+
+use std::{collections::HashMap, time::Duration};
+
+use rig::{completion::ToolDefinition, tool::Tool};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::surface_param;
+use crate::{
+    error::SenseiError,
+    lua,
+    rcon_ext::{execute_lua_json, SharedRcon},
+};
+
+/// Seconds between occupancy samples. Trains dwell at a stop far longer than this.
+const SAMPLE_INTERVAL_SECS: u64 = 2;
+/// Longest sample window — the agent turn blocks for the whole window.
+const MAX_SAMPLE_SECS: u64 = 120;
+/// Game ticks per hour at normal game speed.
+const TICKS_PER_HOUR: f64 = 216_000.0;
+
+/// Lists train stops with limits, circuit conditions, and sampled deliveries per hour.
+pub struct GetTrainStops {
+    pub(crate) rcon: SharedRcon,
+}
+
+impl GetTrainStops {
+    pub const fn new(rcon: SharedRcon) -> Self {
+        Self { rcon }
+    }
+}
+
+/// Arguments for [`GetTrainStops`]. All optional.
+#[derive(Debug, Deserialize)]
+pub struct GetTrainStopsArgs {
+    /// Only stops with this exact name. Defaults to every stop.
+    pub name: Option<String>,
+    /// Seconds to watch stops for arrivals. Defaults to 0 (no sampling), capped at 120.
+    pub sample_seconds: Option<u64>,
+    #[doc = surface_arg_doc!()]
+    pub surface: Option<String>,
+}
+
+/// A train stop's circuit network control settings.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct StopCircuit {
+    /// Whether the circuit condition enables/disables the stop.
+    pub enable_disable: bool,
+    /// The condition, e.g. "iron-plate < 1000", when enable/disable is on.
+    pub condition: Option<String>,
+    /// Whether the circuit condition currently disables the stop.
+    #[serde(default)]
+    pub disabled: bool,
+    /// Whether the trains limit is set from a circuit signal.
+    #[serde(default)]
+    pub sets_trains_limit: bool,
+}
+
+/// A single train stop's state snapshot.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct TrainStopInfo {
+    /// Entity `unit_number`, unique per stop.
+    pub id: u64,
+    /// Station name shown on the map.
+    pub name: String,
+    /// World x coordinate.
+    pub x: f64,
+    /// World y coordinate.
+    pub y: f64,
+    /// Max trains allowed to target this stop, or `None` if unlimited.
+    pub trains_limit: Option<u32>,
+    /// Trains currently heading to or stopped at this stop.
+    pub trains_count: u32,
+    /// Id of the train stopped here, if any.
+    pub stopped_train: Option<u32>,
+    /// Circuit settings, or `None` when not wired.
+    pub circuit: Option<StopCircuit>,
+    /// Train arrivals seen while sampling.
+    #[serde(default)]
+    pub arrivals: Option<u32>,
+    /// Arrivals scaled to one hour of game time.
+    #[serde(default)]
+    pub deliveries_per_hour: Option<f64>,
+}
+
+/// Top-level response: train stops sorted by name.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct TrainStops {
+    /// Game tick of the initial snapshot.
+    pub tick: u64,
+    #[serde(default)]
+    pub stops: Vec<TrainStopInfo>,
+    /// Game seconds the arrival counts cover (0 when not sampled).
+    #[serde(default)]
+    pub sampled_seconds: f64,
+}
+
+/// One occupancy poll: which train is stopped at each stop.
+#[derive(Debug, Deserialize)]
+struct Occupancy {
+    tick: u64,
+    #[serde(default)]
+    stops: Vec<StopOccupant>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StopOccupant {
+    id: u64,
+    train: Option<u32>,
+}
+
+/// Count arrivals in a series of stopped-train samples. A train already present
+/// in the first sample arrived before the window and is not counted.
+fn count_arrivals(samples: &[Option<u32>]) -> u32 {
+    let count = samples
+        .windows(2)
+        .filter(|w| w[1].is_some() && w[1] != w[0])
+        .count();
+    u32::try_from(count).unwrap_or(u32::MAX)
+}
+
+impl Tool for GetTrainStops {
+    const NAME: &'static str = "get_train_stops";
+    type Error = SenseiError;
+    type Args = GetTrainStopsArgs;
+    type Output = TrainStops;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "get_train_stops".to_string(),
+            description: "Get train stops by name with trains limit, trains en route, the train currently stopped, and the circuit enable/disable condition. With sample_seconds set, watches the stops and reports train arrivals and deliveries per hour, to find starving or overloaded stations; sampling waits in real time, so the call takes that long.".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "name": {
+                        "type": "string",
+                        "description": "Exact station name to inspect (default: all stops)"
+                    },
+                    "sample_seconds": {
+                        "type": "integer",
+                        "description": "Seconds to watch for train arrivals, waiting in real time (default: 0, no sampling; max: 120). Longer windows give steadier rates."
+                    },
+                    "surface": surface_param()
+                }
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let surface = args.surface.as_deref();
+        let lua = lua::train_stops(args.name.as_deref(), surface);
+        let json = execute_lua_json(&self.rcon, &lua).await?;
+        let mut result: TrainStops = serde_json::from_str(&json)?;
+        result
+            .stops
+            .sort_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id)));
+
+        let sample_seconds = args.sample_seconds.unwrap_or(0).min(MAX_SAMPLE_SECS);
+        if sample_seconds == 0 || result.stops.is_empty() {
+            return Ok(result);
+        }
+
+        let mut samples: HashMap<u64, Vec<Option<u32>>> = result
+            .stops
+            .iter()
+            .map(|stop| (stop.id, vec![stop.stopped_train]))
+            .collect();
+        let mut last_tick = result.tick;
+        let occupancy_lua = lua::train_stop_occupancy(surface);
+        for _ in 0..sample_seconds.div_ceil(SAMPLE_INTERVAL_SECS) {
+            tokio::time::sleep(Duration::from_secs(SAMPLE_INTERVAL_SECS)).await;
+            let json = execute_lua_json(&self.rcon, &occupancy_lua).await?;
+            let poll: Occupancy = serde_json::from_str(&json)?;
+            last_tick = poll.tick;
+            for occupant in poll.stops {
+                if let Some(series) = samples.get_mut(&occupant.id) {
+                    series.push(occupant.train);
+                }
+            }
+        }
+
+        let ticks = last_tick.saturating_sub(result.tick);
+        result.sampled_seconds = ticks as f64 / 60.0;
+        for stop in &mut result.stops {
+            let arrivals = samples.get(&stop.id).map_or(0, |s| count_arrivals(s));
+            stop.arrivals = Some(arrivals);
+            stop.deliveries_per_hour =
+                (ticks > 0).then(|| f64::from(arrivals) * TICKS_PER_HOUR / ticks as f64);
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_train_stops() {
+        let json = r#"{"tick":360000,"stops":[
+            {"id":101,"name":"Iron Drop","x":10,"y":20,"trains_limit":2,"trains_count":1,"stopped_train":7,
+             "circuit":{"enable_disable":true,"condition":"iron-plate < 2000","disabled":false,"sets_trains_limit":false}},
+            {"id":102,"name":"Iron Pickup","x":-300,"y":40,"trains_count":0}
+        ]}"#;
+        let result: TrainStops = serde_json::from_str(json).unwrap();
+        assert_eq!(result.stops[0].trains_limit, Some(2));
+        assert_eq!(
+            result.stops[0]
+                .circuit
+                .as_ref()
+                .unwrap()
+                .condition
+                .as_deref(),
+            Some("iron-plate < 2000")
+        );
+        assert_eq!(result.stops[1].trains_limit, None);
+        assert_eq!(result.stops[1].circuit, None);
+        assert_eq!(result.stops[1].deliveries_per_hour, None);
+    }
+
+    #[test]
+    fn test_parse_no_train_stops() {
+        let result: TrainStops = serde_json::from_str(r#"{"tick":360000}"#).unwrap();
+        assert!(result.stops.is_empty());
+        assert_eq!(result.sampled_seconds, 0.0);
+    }
+
+    #[test]
+    fn test_count_arrivals_ignores_train_already_present() {
+        assert_eq!(count_arrivals(&[Some(7), Some(7), None, None]), 0);
+    }
+
+    #[test]
+    fn test_count_arrivals_counts_new_trains() {
+        let samples = [None, Some(3), Some(3), None, Some(5), Some(5), Some(9)];
+        assert_eq!(count_arrivals(&samples), 3);
+    }
+
+    #[test]
+    fn test_count_arrivals_empty_station() {
+        assert_eq!(count_arrivals(&[None, None, None]), 0);
+        assert_eq!(count_arrivals(&[]), 0);
+    }
+}
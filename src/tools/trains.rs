@@ -0,0 +1,232 @@
+//! Tool for inspecting the train fleet: state, stations, schedule, cargo, and fuel.
+//!
+//! Reads trains from `game.train_manager.get_trains` for the force on the target
+//! surface. Each train reports its `defines.train_state` name (on_the_path,
+//! wait_signal, no_path, destination_full, ...), the station it is stopped at and
+//! the next one in its schedule, item and fluid cargo, and per-locomotive fuel.
+//!
+//! Whether a train is stuck is decided on the Rust side from its state, manual
+//! mode, and locomotive fuel.
+
+use std::collections::BTreeMap;
+
+use rig::{completion::ToolDefinition, tool::Tool};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::{inventory::InventoryItem, surface_param};
+use crate::{
+    error::SenseiError,
+    lua,
+    rcon_ext::{execute_lua_json, SharedRcon},
+};
+
+/// Lists trains with their state, schedule, cargo, and locomotive fuel.
+pub struct GetTrains {
+    pub(crate) rcon: SharedRcon,
+}
+
+impl GetTrains {
+    pub const fn new(rcon: SharedRcon) -> Self {
+        Self { rcon }
+    }
+}
+
+/// Arguments for [`GetTrains`]. All optional.
+#[derive(Debug, Deserialize)]
+pub struct GetTrainsArgs {
+    /// Max trains to list individually. Defaults to 30. State counts always cover every train.
+    pub limit: Option<u32>,
+    #[doc = surface_arg_doc!()]
+    pub surface: Option<String>,
+}
+
+/// Fluid carried in fluid wagons.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct FluidCargo {
+    pub name: String,
+    pub amount: f64,
+}
+
+/// One locomotive's fuel state.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct LocomotiveFuel {
+    /// Locomotive prototype name.
+    pub name: String,
+    /// Fuel inventory contents.
+    #[serde(default)]
+    pub fuel: Vec<InventoryItem>,
+    /// Seconds of full-power running left on its fuel, or `None` without a burner.
+    pub fuel_seconds: Option<f64>,
+}
+
+/// A single train's state snapshot.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct TrainInfo {
+    /// Unique train id.
+    pub id: u32,
+    /// `defines.train_state` name (e.g. "on_the_path", "wait_station", "no_path").
+    pub state: String,
+    /// Whether the train is under manual control.
+    pub manual_mode: bool,
+    /// Current speed in tiles per tick.
+    pub speed: f64,
+    /// Front stock position.
+    pub x: Option<f64>,
+    pub y: Option<f64>,
+    /// Station the train is stopped at, if any.
+    pub station: Option<String>,
+    /// Next station in the schedule.
+    pub next_station: Option<String>,
+    /// Scheduled station names in order.
+    #[serde(default)]
+    pub schedule: Vec<String>,
+    /// Item cargo across all wagons.
+    #[serde(default)]
+    pub cargo: Vec<InventoryItem>,
+    /// Fluid cargo across all fluid wagons.
+    #[serde(default)]
+    pub fluids: Vec<FluidCargo>,
+    #[serde(default)]
+    pub locomotives: Vec<LocomotiveFuel>,
+    /// Whether the train needs attention (see [`TrainInfo::is_stuck`]).
+    #[serde(default)]
+    pub stuck: bool,
+}
+
+impl TrainInfo {
+    /// A train is stuck when it has no path or schedule, its destination is full,
+    /// or every locomotive is out of fuel. Manual trains are the player's business.
+    fn is_stuck(&self) -> bool {
+        if self.manual_mode {
+            return false;
+        }
+        let blocked = matches!(
+            self.state.as_str(),
+            "no_path" | "path_lost" | "no_schedule" | "destination_full"
+        );
+        let out_of_fuel = !self.locomotives.is_empty()
+            && self
+                .locomotives
+                .iter()
+                .all(|l| l.fuel_seconds.is_some_and(|s| s <= 0.0));
+        blocked || out_of_fuel
+    }
+}
+
+/// Top-level response: train states across the surface plus individual trains.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct Trains {
+    /// Total trains on the surface.
+    pub total: u32,
+    /// Train count per state name.
+    #[serde(default)]
+    pub states: BTreeMap<String, u32>,
+    #[serde(default)]
+    pub trains: Vec<TrainInfo>,
+}
+
+impl Tool for GetTrains {
+    const NAME: &'static str = "get_trains";
+    type Error = SenseiError;
+    type Args = GetTrainsArgs;
+    type Output = Trains;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "get_trains".to_string(),
+            description: "Get trains with their state (on_the_path, wait_signal, wait_station, no_path, destination_full, ...), current and next station, schedule, cargo, and locomotive fuel. Flags stuck trains and counts trains per state.".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of trains to list individually (default: 30). State counts always include every train."
+                    },
+                    "surface": surface_param()
+                }
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let limit = args.limit.unwrap_or(30);
+        let lua = lua::trains(limit, args.surface.as_deref());
+        let json = execute_lua_json(&self.rcon, &lua).await?;
+        let mut result: Trains = serde_json::from_str(&json)?;
+        for train in &mut result.trains {
+            train.stuck = train.is_stuck();
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn train(json: &str) -> TrainInfo {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_parse_trains() {
+        let json = r#"{"total":2,"states":{"wait_station":1,"no_path":1},"trains":[
+            {"id":1,"state":"wait_station","manual_mode":false,"speed":0,"x":100.5,"y":-20,
+             "station":"Iron Pickup","next_station":"Iron Drop","schedule":["Iron Pickup","Iron Drop"],
+             "cargo":[{"name":"iron-ore","count":4000}],
+             "locomotives":[{"name":"locomotive","fuel":[{"name":"coal","count":50}],"fuel_seconds":1000}]},
+            {"id":2,"state":"no_path","manual_mode":false,"speed":0,"x":-40,"y":12,
+             "next_station":"Oil Drop","schedule":["Oil Pickup","Oil Drop"],
+             "fluids":[{"name":"crude-oil","amount":25000}],
+             "locomotives":[{"name":"locomotive","fuel_seconds":300}]}
+        ]}"#;
+        let result: Trains = serde_json::from_str(json).unwrap();
+        assert_eq!(result.states["no_path"], 1);
+        assert_eq!(result.trains[0].station.as_deref(), Some("Iron Pickup"));
+        assert_eq!(result.trains[0].cargo[0].count, 4000);
+        assert_eq!(result.trains[1].station, None);
+        assert!(result.trains[1].cargo.is_empty());
+        assert!((result.trains[1].fluids[0].amount - 25000.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_parse_no_trains() {
+        let result: Trains = serde_json::from_str(r#"{"total":0}"#).unwrap();
+        assert!(result.states.is_empty());
+        assert!(result.trains.is_empty());
+    }
+
+    #[test]
+    fn test_blocked_states_are_stuck() {
+        let t = train(
+            r#"{"id":3,"state":"destination_full","manual_mode":false,"speed":0,
+                "locomotives":[{"name":"locomotive","fuel_seconds":500}]}"#,
+        );
+        assert!(t.is_stuck());
+    }
+
+    #[test]
+    fn test_moving_train_is_not_stuck() {
+        let t = train(
+            r#"{"id":4,"state":"on_the_path","manual_mode":false,"speed":1.2,
+                "locomotives":[{"name":"locomotive","fuel_seconds":500}]}"#,
+        );
+        assert!(!t.is_stuck());
+    }
+
+    #[test]
+    fn test_unfueled_train_is_stuck() {
+        let t = train(
+            r#"{"id":5,"state":"wait_signal","manual_mode":false,"speed":0,
+                "locomotives":[{"name":"locomotive","fuel_seconds":0},{"name":"locomotive","fuel_seconds":0}]}"#,
+        );
+        assert!(t.is_stuck());
+    }
+
+    #[test]
+    fn test_manual_train_is_not_stuck() {
+        let t = train(r#"{"id":6,"state":"no_schedule","manual_mode":true,"speed":0}"#);
+        assert!(!t.is_stuck());
+    }
+}
@@ -1,4 +1,4 @@
-//! Integration tests for all 10 Rig tools against a live Factorio instance.
+//! Integration tests for all 28 Rig tools against a live Factorio instance.
 //!
 //! These tests require a running Factorio server with RCON enabled.
 //! Run with: `cargo test -- --ignored`
@@ -57,10 +57,48 @@ async fn test_get_production_stats() {
     let result = tool
         .call(GetProductionStatsArgs {
             item: "iron-plate".to_string(),
+            window: Some(FlowWindow::OneMinute),
+            surface: None,
         })
         .await
         .unwrap();
     assert_eq!(result.item, "iron-plate");
+    assert_eq!(result.window, FlowWindow::OneMinute);
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_get_production_overview() {
+    let rcon = shared_rcon().await;
+    let tool = GetProductionOverview::new(rcon);
+    let result = tool
+        .call(GetProductionOverviewArgs {
+            window: None,
+            top: Some(5),
+            surface: None,
+        })
+        .await
+        .unwrap();
+    assert!(result.top_produced.len() <= 5);
+    assert!(result.deficits.len() <= 5);
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_get_fluid_production() {
+    let rcon = shared_rcon().await;
+    let tool = GetFluidProduction::new(rcon);
+    let result = tool
+        .call(GetFluidProductionArgs {
+            fluid: Some("water".to_string()),
+            window: None,
+            top: None,
+            surface: None,
+        })
+        .await
+        .unwrap();
+    // The requested fluid is always echoed back, even if it never flowed
+    assert_eq!(result.fluid.unwrap().name, "water");
 }
 
 #[tokio::test]
@@ -68,7 +106,10 @@ async fn test_get_production_stats() {
 async fn test_get_power_stats() {
     let rcon = shared_rcon().await;
     let tool = GetPowerStats::new(rcon);
-    let result = tool.call(GetPowerStatsArgs {}).await.unwrap();
+    let result = tool
+        .call(GetPowerStatsArgs { surface: None })
+        .await
+        .unwrap();
     // Satisfaction should be between 0 and 1 (inclusive)
     assert!(result.satisfaction >= 0.0 && result.satisfaction <= 1.0);
 }
@@ -105,20 +146,54 @@ async fn test_get_nearby_entities() {
     let rcon = shared_rcon().await;
     let tool = GetNearbyEntities::new(rcon);
     let result = tool
-        .call(GetNearbyEntitiesArgs { radius: Some(10.0) })
+        .call(GetNearbyEntitiesArgs {
+            radius: Some(10.0),
+            area: None,
+            names: None,
+            types: None,
+            cursor: None,
+            limit: None,
+            census: None,
+            surface: None,
+        })
         .await
         .unwrap();
     // Just verify it parses — may be empty in a fresh game
     let _ = result.entities;
 }
 
+#[tokio::test]
+#[ignore]
+async fn test_get_nearby_entities_census() {
+    let rcon = shared_rcon().await;
+    let tool = GetNearbyEntities::new(rcon);
+    let result = tool
+        .call(GetNearbyEntitiesArgs {
+            radius: None,
+            area: None,
+            names: None,
+            types: None,
+            cursor: None,
+            limit: None,
+            census: Some(true),
+            surface: None,
+        })
+        .await
+        .unwrap();
+    assert!(result.entities.is_empty());
+    assert_eq!(result.counts.values().sum::<u32>(), result.total);
+}
+
 #[tokio::test]
 #[ignore]
 async fn test_get_nearby_resources() {
     let rcon = shared_rcon().await;
     let tool = GetNearbyResources::new(rcon);
     let result = tool
-        .call(GetNearbyResourcesArgs { radius: Some(50.0) })
+        .call(GetNearbyResourcesArgs {
+            radius: Some(50.0),
+            surface: None,
+        })
         .await
         .unwrap();
     // Just verify it parses — should find some ore on nauvis spawn
@@ -131,22 +206,381 @@ async fn test_get_assemblers() {
     let rcon = shared_rcon().await;
     let tool = GetAssemblers::new(rcon);
     let result = tool
-        .call(GetAssemblersArgs { limit: Some(10) })
+        .call(GetAssemblersArgs {
+            limit: Some(10),
+            summary: None,
+            surface: None,
+        })
         .await
         .unwrap();
     // Just verify it parses — may be empty early game
     let _ = result.assemblers;
 }
 
+#[tokio::test]
+#[ignore]
+async fn test_get_assemblers_summary() {
+    let rcon = shared_rcon().await;
+    let tool = GetAssemblers::new(rcon);
+    let result = tool
+        .call(GetAssemblersArgs {
+            limit: None,
+            summary: Some(true),
+            surface: None,
+        })
+        .await
+        .unwrap();
+    assert!(result.assemblers.is_empty());
+    for group in result.groups {
+        assert_eq!(group.statuses.values().sum::<u32>(), group.machines);
+    }
+}
+
 #[tokio::test]
 #[ignore]
 async fn test_get_furnaces() {
     let rcon = shared_rcon().await;
     let tool = GetFurnaces::new(rcon);
     let result = tool
-        .call(GetFurnacesArgs { limit: Some(10) })
+        .call(GetFurnacesArgs {
+            limit: Some(10),
+            summary: None,
+            surface: None,
+        })
         .await
         .unwrap();
     // Just verify it parses — may be empty early game
     let _ = result.furnaces;
 }
+
+#[tokio::test]
+#[ignore]
+async fn test_get_furnaces_summary() {
+    let rcon = shared_rcon().await;
+    let tool = GetFurnaces::new(rcon);
+    let result = tool
+        .call(GetFurnacesArgs {
+            limit: None,
+            summary: Some(true),
+            surface: None,
+        })
+        .await
+        .unwrap();
+    assert!(result.furnaces.is_empty());
+    for group in result.groups {
+        assert_eq!(group.statuses.values().sum::<u32>(), group.machines);
+    }
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_list_surfaces() {
+    let rcon = shared_rcon().await;
+    let tool = ListSurfaces::new(rcon);
+    let result = tool.call(ListSurfacesArgs {}).await.unwrap();
+    assert!(result.surfaces.iter().any(|s| s.name == "nauvis"));
+    assert_eq!(result.surfaces.iter().filter(|s| s.player_here).count(), 1);
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_get_power_stats_on_named_surface() {
+    let rcon = shared_rcon().await;
+    let tool = GetPowerStats::new(rcon);
+    let result = tool
+        .call(GetPowerStatsArgs {
+            surface: Some("nauvis".to_string()),
+        })
+        .await
+        .unwrap();
+    assert!(result.satisfaction >= 0.0 && result.satisfaction <= 1.0);
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_get_mining_drills() {
+    let rcon = shared_rcon().await;
+    let tool = GetMiningDrills::new(rcon);
+    let result = tool
+        .call(GetMiningDrillsArgs {
+            limit: Some(5),
+            surface: None,
+        })
+        .await
+        .unwrap();
+    assert!(result.drills.len() <= 5);
+    for group in result.groups {
+        assert_eq!(group.statuses.values().sum::<u32>(), group.drills);
+    }
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_get_technology() {
+    let rcon = shared_rcon().await;
+    let tool = GetTechnology::new(rcon);
+    let result = tool
+        .call(GetTechnologyArgs {
+            name: Some("automation".to_string()),
+        })
+        .await
+        .unwrap();
+    let tech = result.technology.expect("automation exists in every game");
+    assert_eq!(tech.name, "automation");
+    assert!(tech
+        .unlocks_recipes
+        .iter()
+        .any(|r| r == "assembling-machine-1"));
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_get_labs() {
+    let rcon = shared_rcon().await;
+    let tool = GetLabs::new(rcon);
+    let result = tool
+        .call(GetLabsArgs {
+            limit: Some(5),
+            surface: None,
+        })
+        .await
+        .unwrap();
+    assert!(result.labs.len() <= 5);
+    assert!(result.summary.starved <= result.summary.labs);
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_get_trains() {
+    let rcon = shared_rcon().await;
+    let tool = GetTrains::new(rcon);
+    let result = tool
+        .call(GetTrainsArgs {
+            limit: Some(5),
+            surface: None,
+        })
+        .await
+        .unwrap();
+    assert!(result.trains.len() <= 5);
+    assert_eq!(result.states.values().sum::<u32>(), result.total);
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_get_train_stops() {
+    let rcon = shared_rcon().await;
+    let tool = GetTrainStops::new(rcon);
+    let result = tool
+        .call(GetTrainStopsArgs {
+            name: None,
+            sample_seconds: Some(4),
+            surface: None,
+        })
+        .await
+        .unwrap();
+    for stop in result.stops {
+        assert!(stop.arrivals.is_some());
+    }
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_get_logistic_networks() {
+    let rcon = shared_rcon().await;
+    let tool = GetLogisticNetworks::new(rcon);
+    let result = tool
+        .call(GetLogisticNetworksArgs { surface: None })
+        .await
+        .unwrap();
+    for network in result.networks {
+        assert!(network.available_logistic_robots <= network.total_logistic_robots);
+    }
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_get_construction_backlog() {
+    let rcon = shared_rcon().await;
+    let tool = GetConstructionBacklog::new(rcon);
+    let result = tool
+        .call(GetConstructionBacklogArgs {
+            radius: Some(100.0),
+            surface: None,
+        })
+        .await
+        .unwrap();
+    for item in result.items {
+        assert_eq!(item.missing, item.needed.saturating_sub(item.available));
+    }
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_get_belts() {
+    let rcon = shared_rcon().await;
+    let tool = GetBelts::new(rcon);
+    let result = tool
+        .call(GetBeltsArgs {
+            x: None,
+            y: None,
+            radius: Some(20.0),
+            follow: None,
+            limit: Some(50),
+            surface: None,
+        })
+        .await
+        .unwrap();
+    assert!(result.belts.len() <= 50);
+    for lane in result.lanes {
+        assert!(lane.fill_ratio >= 0.0 && lane.fill_ratio <= 1.0);
+    }
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_get_inserters() {
+    let rcon = shared_rcon().await;
+    let assemblers = GetAssemblers::new(rcon.clone())
+        .call(GetAssemblersArgs {
+            limit: Some(1),
+            summary: None,
+            surface: None,
+        })
+        .await
+        .unwrap();
+    let Some(machine) = assemblers.assemblers.first() else {
+        return;
+    };
+    let tool = GetInserters::new(rcon);
+    let result = tool
+        .call(GetInsertersArgs {
+            x: machine.x,
+            y: machine.y,
+            surface: None,
+        })
+        .await
+        .unwrap();
+    for inserter in result.inserters {
+        assert!(inserter.role == "input" || inserter.role == "output");
+    }
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_get_pollution() {
+    let rcon = shared_rcon().await;
+    let tool = GetPollution::new(rcon);
+    let result = tool
+        .call(GetPollutionArgs {
+            chunk_radius: Some(2),
+            surface: None,
+        })
+        .await
+        .unwrap();
+    assert!(result.evolution.factor >= 0.0 && result.evolution.factor <= 1.0);
+    assert!(result.chunks.scanned <= 25);
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_get_defense() {
+    let rcon = shared_rcon().await;
+    let tool = GetDefense::new(rcon);
+    let result = tool
+        .call(GetDefenseArgs {
+            radius: Some(200.0),
+            distance: None,
+            surface: None,
+        })
+        .await
+        .unwrap();
+    let summarized: u32 = result.by_direction.values().map(|d| d.turrets).sum();
+    assert_eq!(summarized as usize, result.turrets.len());
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_get_alerts() {
+    let rcon = shared_rcon().await;
+    let tool = GetAlerts::new(rcon);
+    let result = tool.call(GetAlertsArgs { surface: None }).await.unwrap();
+    let counted: u32 = result.groups.iter().map(|g| g.count).sum();
+    assert_eq!(counted, result.total);
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_find_item() {
+    let rcon = shared_rcon().await;
+    let tool = FindItem::new(rcon);
+    let result = tool
+        .call(FindItemArgs {
+            item: "iron-plate".to_string(),
+            limit: Some(5),
+            surface: None,
+        })
+        .await
+        .unwrap();
+    assert!(result.locations.len() <= 5);
+    let listed: u64 = result.locations.iter().map(|l| l.count).sum();
+    assert!(listed <= result.total);
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_get_fluid_system() {
+    let rcon = shared_rcon().await;
+    let pipes = GetNearbyEntities::new(rcon.clone())
+        .call(GetNearbyEntitiesArgs {
+            radius: Some(100.0),
+            area: None,
+            names: None,
+            types: Some(vec!["pipe".to_string(), "storage-tank".to_string()]),
+            cursor: None,
+            limit: Some(1),
+            census: None,
+            surface: None,
+        })
+        .await
+        .unwrap();
+    // Nothing to inspect in a fresh game without pipes
+    let Some(pipe) = pipes.entities.first() else {
+        return;
+    };
+    let tool = GetFluidSystem::new(rcon);
+    let result = tool
+        .call(GetFluidSystemArgs {
+            x: pipe.x,
+            y: pipe.y,
+            limit: Some(100),
+            surface: None,
+        })
+        .await
+        .unwrap();
+    assert!(result.entities >= 1);
+    assert!(result.entities <= 100);
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_solve_oil_balance() {
+    let rcon = shared_rcon().await;
+    let tool = SolveOilBalance::new(rcon);
+    let result = tool
+        .call(SolveOilBalanceArgs {
+            advanced_refineries: Some(1),
+            basic_refineries: None,
+            coal_liquefaction: None,
+            heavy_oil_crackers: None,
+            light_oil_crackers: None,
+            consumers: None,
+            refinery_speed: None,
+            chemical_plant_speed: None,
+        })
+        .await
+        .unwrap();
+    // Vanilla advanced oil processing: 25 heavy, 45 light, 55 petroleum per 5s
+    assert_eq!(result.fluids.len(), 3);
+    assert_eq!(result.fluids[0].status, "backs_up");
+    assert!(result.recommended.heavy_oil_crackers >= 1);
+}